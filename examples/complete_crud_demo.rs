@@ -201,15 +201,7 @@ async fn main() -> Result<()> {
     println!("Users after delete: {}", count_after);
 
     // DELETE: Remove by condition
-    let users_to_delete = User::query(backend)
-        .where_eq("age", QueryValue::I32(30))
-        .get()
-        .await?;
-    let mut deleted_count = 0;
-    for user in users_to_delete {
-        user.delete(backend).await?;
-        deleted_count += 1;
-    }
+    let deleted_count = User::delete_where_cmp(backend, "age", ">", QueryValue::I32(30)).await?;
     println!("\nDeleted {} users with age > 30", deleted_count);
     
     let final_count = User::count(backend).await?;
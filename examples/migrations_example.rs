@@ -60,6 +60,7 @@ impl Migration for CreatePostsTable {
             table.timestamps();
             
             table.foreign_key(ForeignKey {
+                name: None,
                 column: "user_id".to_string(),
                 references_table: "users".to_string(),
                 references_column: "id".to_string(),
@@ -106,23 +107,14 @@ impl Migration for CreateTagsTable {
             table.big_integer("post_id");
             table.big_integer("tag_id");
             
-            table.foreign_key(ForeignKey {
-                column: "post_id".to_string(),
-                references_table: "posts".to_string(),
-                references_column: "id".to_string(),
-                on_delete: Some(ForeignKeyAction::Cascade),
-                on_update: None,
-            });
-            
-            table.foreign_key(ForeignKey {
-                column: "tag_id".to_string(),
-                references_table: "tags".to_string(),
-                references_column: "id".to_string(),
-                on_delete: Some(ForeignKeyAction::Cascade),
-                on_update: None,
-            });
+            table
+                .foreign_key_to("post_id", "posts", "id")
+                .on_delete(ForeignKeyAction::Cascade);
+            table
+                .foreign_key_to("tag_id", "tags", "id")
+                .on_delete(ForeignKeyAction::Cascade);
             
-            table.index("idx_post_tags", vec!["post_id".to_string(), "tag_id".to_string()], true);
+            table.unique("uq_post_tags", vec!["post_id".to_string(), "tag_id".to_string()]);
         });
         
         Ok(())
@@ -94,9 +94,9 @@ fn main() -> Result<()> {
         .distinct()
         .select(&[
             Column::new("categories.name", ColumnType::Text),
-            Column::new("COUNT(DISTINCT products.id) as product_count", ColumnType::BigInteger),
-            Column::new("SUM(order_items.quantity) as total_sold", ColumnType::BigInteger),
         ])
+        .select_aggregate("product_count", "COUNT", "products.id", true)
+        .select_aggregate("total_sold", "SUM", "order_items.quantity", false)
         .from("categories")
         .inner_join("products", "products.category_id = categories.id")
         .left_join("order_items", "order_items.product_id = products.id")
@@ -0,0 +1,46 @@
+use orm::connection::ConnectOptions;
+use orm::prelude::*;
+use orm::query::QueryValue;
+use std::time::{Duration, Instant};
+
+const ITERATIONS: i64 = 10_000;
+
+/// Run the same parameterized query ITERATIONS times and return the elapsed time
+async fn run(options: &ConnectOptions) -> Result<Duration> {
+    let db = Database::connect_with("sqlite::memory:", &options.clone().max_connections(1)).await?;
+    let backend = db.backend();
+    backend
+        .execute("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL)", &[])
+        .await?;
+    backend
+        .execute("INSERT INTO users (name) VALUES (?)", &[QueryValue::String("Alice".to_string())])
+        .await?;
+
+    let start = Instant::now();
+    for i in 0..ITERATIONS {
+        backend
+            .fetch_one_params("SELECT id, name FROM users WHERE id = ? OR ? < 0", &[QueryValue::I64(1), QueryValue::I64(i)])
+            .await?;
+    }
+    Ok(start.elapsed())
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("Prepared Statement Cache Benchmark");
+    println!("==================================\n");
+
+    let uncached = run(&ConnectOptions::new().statement_cache_capacity(0)).await?;
+    let cached = run(&ConnectOptions::new()).await?;
+
+    for (label, elapsed) in [("cache disabled", uncached), ("cache enabled ", cached)] {
+        println!(
+            "{}: {:?} total, {:?} per query",
+            label,
+            elapsed,
+            elapsed / ITERATIONS as u32
+        );
+    }
+
+    Ok(())
+}
@@ -1,9 +1,12 @@
 pub mod mysql;
+pub mod replicated;
 pub mod sqlite;
 
+pub use replicated::ReplicatedBackend;
+
 use crate::error::Result;
 use crate::query::builder::{Dialect, QueryBuilderEnum};
-use crate::query::QueryValue;
+use crate::query::{QueryExecutor, QueryValue};
 use async_trait::async_trait;
 
 /// Trait representing a database backend
@@ -18,6 +21,9 @@ pub trait Backend: Send + Sync + 'static {
     /// Create a query builder for this backend
     fn query_builder(&self) -> QueryBuilderEnum;
 
+    /// Create an executor for the given SQL bound to this backend's pool
+    fn executor(&self, sql: &str) -> QueryExecutor;
+
     /// Execute raw SQL (DEPRECATED - vulnerable to SQL injection, use execute instead)
     #[deprecated(note = "Use execute() with parameters for SQL injection protection")]
     async fn execute_raw(&self, sql: &str) -> Result<u64>;
@@ -25,6 +31,14 @@ pub trait Backend: Send + Sync + 'static {
     /// Execute SQL with parameters (safe from SQL injection)
     async fn execute(&self, sql: &str, params: &[QueryValue]) -> Result<u64>;
 
+    /// Execute an INSERT and return the generated key, or `None` if no row was inserted
+    ///
+    /// Reads the key from the connection that ran the statement, so it is safe under a pool.
+    async fn execute_returning(&self, sql: &str, params: &[QueryValue]) -> Result<Option<i64>>;
+
+    /// Execute a script of multiple SQL statements (no parameters), returning total rows affected
+    async fn execute_batch(&self, sql: &str) -> Result<u64>;
+
     /// Fetch all rows from a query as JSON values (DEPRECATED - vulnerable to SQL injection, use fetch_all_params)
     #[deprecated(note = "Use fetch_all_params() with parameters for SQL injection protection")]
     async fn fetch_all(&self, sql: &str) -> Result<Vec<serde_json::Value>>;
@@ -39,9 +53,30 @@ pub trait Backend: Send + Sync + 'static {
     /// Fetch one row with parameters (safe from SQL injection)
     async fn fetch_one_params(&self, sql: &str, params: &[QueryValue]) -> Result<Option<serde_json::Value>>;
 
+    /// Return the query plan for a statement without executing it
+    async fn explain(&self, sql: &str, params: &[QueryValue]) -> Result<Vec<serde_json::Value>>;
+
+    /// Whether a table (or view) with this name exists
+    async fn table_exists(&self, table: &str) -> Result<bool>;
+
+    /// Whether `table` exists and has a column with this name
+    async fn column_exists(&self, table: &str, column: &str) -> Result<bool>;
+
     /// Begin a new transaction
     async fn begin_transaction(&self) -> Result<crate::transaction::Transaction>;
 
+    /// Begin a read-only transaction, giving a consistent snapshot across its reads.
+    /// Replicated backends run it on a replica.
+    ///
+    /// SQLite databases not in WAL mode block writers while the snapshot is open.
+    async fn begin_read_transaction(&self) -> Result<crate::transaction::Transaction>;
+
+    /// Close the connection pool, waiting for in-flight queries to finish
+    async fn close(&self);
+
+    /// Whether the connection pool has been closed
+    fn is_closed(&self) -> bool;
+
     /// Check if the backend supports a specific feature
     fn supports_feature(&self, feature: BackendFeature) -> bool;
 }
@@ -69,6 +104,32 @@ impl<P> GenericBackend<P> {
     }
 }
 
+impl<DB: sqlx::Database> GenericBackend<sqlx::Pool<DB>> {
+    /// Fail with a connection error once the pool has been closed
+    fn ensure_open(&self) -> Result<()> {
+        ensure_pool_open(&self.pool)
+    }
+}
+
+/// Fail with a connection error once `pool` has been closed
+pub(crate) fn ensure_pool_open<DB: sqlx::Database>(pool: &sqlx::Pool<DB>) -> Result<()> {
+    if pool.is_closed() {
+        return Err(crate::error::Error::ConnectionError(
+            "Connection pool is closed".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Map a sqlx error from a pool operation, reporting acquire timeouts as
+/// `Error::PoolTimeout` with the pool's configured timeout
+pub(crate) fn map_pool_error<DB: sqlx::Database>(pool: &sqlx::Pool<DB>) -> impl Fn(sqlx::Error) -> crate::error::Error + '_ {
+    move |e| match e {
+        sqlx::Error::PoolTimedOut => crate::error::Error::PoolTimeout(pool.options().get_acquire_timeout()),
+        e => e.into(),
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BackendFeature {
     Transactions,
@@ -77,6 +138,7 @@ pub enum BackendFeature {
     OnConflict,
     CTE,
     Window,
+    FullJoin,
 }
 
 /// Enum for selecting database backend
@@ -1,7 +1,8 @@
-use crate::backend::{Backend, BackendFeature, GenericBackend};
+use crate::backend::{map_pool_error, Backend, BackendFeature, GenericBackend};
+use crate::connection::ConnectOptions;
 use crate::error::Result;
 use crate::query::builder::{Dialect, QueryBuilderEnum};
-use crate::query::QueryValue;
+use crate::query::{ExecutorPool, QueryExecutor, QueryValue};
 use async_trait::async_trait;
 use sqlx::MySqlPool;
 
@@ -9,7 +10,11 @@ pub type MySQLBackend = GenericBackend<MySqlPool>;
 
 impl MySQLBackend {
     pub async fn connect(url: &str) -> Result<Self> {
-        let pool = MySqlPool::connect(url).await?;
+        Self::connect_with(url, &ConnectOptions::default()).await
+    }
+
+    pub async fn connect_with(url: &str, options: &ConnectOptions) -> Result<Self> {
+        let pool = options.pool_options().connect_with(options.mysql_options(url)?).await?;
         Ok(GenericBackend::new(
             pool,
             url.to_string(),
@@ -33,73 +38,96 @@ impl Backend for MySQLBackend {
         QueryBuilderEnum::new(self.dialect)
     }
 
+    fn executor(&self, sql: &str) -> QueryExecutor {
+        QueryExecutor::with_pool(sql.to_string(), ExecutorPool::MySQL(self.pool().clone()))
+    }
+
     async fn execute_raw(&self, sql: &str) -> Result<u64> {
-        let result = sqlx::query(sql).execute(self.pool()).await?;
+        self.ensure_open()?;
+        let result = sqlx::query(sql).execute(self.pool()).await.map_err(map_pool_error(self.pool()))?;
         Ok(result.rows_affected())
     }
 
     async fn execute(&self, sql: &str, params: &[QueryValue]) -> Result<u64> {
-        let mut query = sqlx::query(sql);
-        for param in params {
-            query = match param {
-                QueryValue::Null => query.bind(Option::<i64>::None),
-                QueryValue::Bool(v) => query.bind(*v),
-                QueryValue::I32(v) => query.bind(*v),
-                QueryValue::I64(v) => query.bind(*v),
-                QueryValue::F64(v) => query.bind(*v),
-                QueryValue::String(v) => query.bind(v.as_str()),
-            };
-        }
-        let result = query.execute(self.pool()).await?;
+        self.ensure_open()?;
+        let query = crate::utils::bind_mysql_params(sqlx::query(sql), params);
+        let result = query.execute(self.pool()).await.map_err(map_pool_error(self.pool()))?;
+        Ok(result.rows_affected())
+    }
+
+    async fn execute_returning(&self, sql: &str, params: &[QueryValue]) -> Result<Option<i64>> {
+        self.ensure_open()?;
+        let query = crate::utils::bind_mysql_params(sqlx::query(sql), params);
+        let result = query.execute(self.pool()).await.map_err(map_pool_error(self.pool()))?;
+        Ok((result.rows_affected() > 0).then(|| result.last_insert_id() as i64))
+    }
+
+    async fn execute_batch(&self, sql: &str) -> Result<u64> {
+        self.ensure_open()?;
+        let result = sqlx::raw_sql(sql).execute(self.pool()).await.map_err(map_pool_error(self.pool()))?;
         Ok(result.rows_affected())
     }
 
     async fn fetch_all(&self, sql: &str) -> Result<Vec<serde_json::Value>> {
-        let rows = sqlx::query(sql).fetch_all(self.pool()).await?;
+        self.ensure_open()?;
+        let rows = sqlx::query(sql).fetch_all(self.pool()).await.map_err(map_pool_error(self.pool()))?;
         Ok(rows.iter().map(crate::utils::mysql_row_to_json).collect())
     }
 
     async fn fetch_all_params(&self, sql: &str, params: &[QueryValue]) -> Result<Vec<serde_json::Value>> {
-        let mut query = sqlx::query(sql);
-        for param in params {
-            query = match param {
-                QueryValue::Null => query.bind(Option::<i64>::None),
-                QueryValue::Bool(v) => query.bind(*v),
-                QueryValue::I32(v) => query.bind(*v),
-                QueryValue::I64(v) => query.bind(*v),
-                QueryValue::F64(v) => query.bind(*v),
-                QueryValue::String(v) => query.bind(v.as_str()),
-            };
-        }
-        let rows = query.fetch_all(self.pool()).await?;
+        self.ensure_open()?;
+        let query = crate::utils::bind_mysql_params(sqlx::query(sql), params);
+        let rows = query.fetch_all(self.pool()).await.map_err(map_pool_error(self.pool()))?;
         Ok(rows.iter().map(crate::utils::mysql_row_to_json).collect())
     }
 
     async fn fetch_one(&self, sql: &str) -> Result<Option<serde_json::Value>> {
-        let row_opt = sqlx::query(sql).fetch_optional(self.pool()).await?;
+        self.ensure_open()?;
+        let row_opt = sqlx::query(sql).fetch_optional(self.pool()).await.map_err(map_pool_error(self.pool()))?;
         Ok(row_opt.as_ref().map(crate::utils::mysql_row_to_json))
     }
 
     async fn fetch_one_params(&self, sql: &str, params: &[QueryValue]) -> Result<Option<serde_json::Value>> {
-        let mut query = sqlx::query(sql);
-        for param in params {
-            query = match param {
-                QueryValue::Null => query.bind(Option::<i64>::None),
-                QueryValue::Bool(v) => query.bind(*v),
-                QueryValue::I32(v) => query.bind(*v),
-                QueryValue::I64(v) => query.bind(*v),
-                QueryValue::F64(v) => query.bind(*v),
-                QueryValue::String(v) => query.bind(v.as_str()),
-            };
-        }
-        let row_opt = query.fetch_optional(self.pool()).await?;
+        self.ensure_open()?;
+        let query = crate::utils::bind_mysql_params(sqlx::query(sql), params);
+        let row_opt = query.fetch_optional(self.pool()).await.map_err(map_pool_error(self.pool()))?;
         Ok(row_opt.as_ref().map(crate::utils::mysql_row_to_json))
     }
 
+    async fn explain(&self, sql: &str, params: &[QueryValue]) -> Result<Vec<serde_json::Value>> {
+        self.fetch_all_params(&format!("EXPLAIN {}", sql), params).await
+    }
+
+    async fn table_exists(&self, table: &str) -> Result<bool> {
+        let sql = "SELECT 1 FROM information_schema.tables WHERE table_schema = DATABASE() AND table_name = ?";
+        Ok(self.fetch_one_params(sql, &[QueryValue::from(table)]).await?.is_some())
+    }
+
+    async fn column_exists(&self, table: &str, column: &str) -> Result<bool> {
+        let sql = "SELECT 1 FROM information_schema.columns \
+                   WHERE table_schema = DATABASE() AND table_name = ? AND column_name = ?";
+        let params = [QueryValue::from(table), QueryValue::from(column)];
+        Ok(self.fetch_one_params(sql, &params).await?.is_some())
+    }
+
     async fn begin_transaction(&self) -> Result<crate::transaction::Transaction> {
+        self.ensure_open()?;
         crate::transaction::Transaction::new_mysql(self.pool()).await
     }
 
+    async fn begin_read_transaction(&self) -> Result<crate::transaction::Transaction> {
+        self.ensure_open()?;
+        crate::transaction::Transaction::new_mysql_read_only(self.pool()).await
+    }
+
+    async fn close(&self) {
+        self.pool().close().await;
+    }
+
+    fn is_closed(&self) -> bool {
+        self.pool().is_closed()
+    }
+
     fn supports_feature(&self, feature: BackendFeature) -> bool {
         match feature {
             BackendFeature::Transactions => true,
@@ -108,6 +136,7 @@ impl Backend for MySQLBackend {
             BackendFeature::OnConflict => false, // Uses INSERT ... ON DUPLICATE KEY
             BackendFeature::CTE => true,        // MySQL 8.0+
             BackendFeature::Window => true,     // MySQL 8.0+
+            BackendFeature::FullJoin => false,
         }
     }
 }
\ No newline at end of file
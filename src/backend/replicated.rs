@@ -0,0 +1,136 @@
+use crate::backend::{Backend, BackendFeature};
+use crate::error::Result;
+use crate::query::builder::QueryBuilderEnum;
+use crate::query::{QueryExecutor, QueryValue};
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Backend that sends writes and transactions to a primary and spreads reads
+/// across replicas round-robin
+pub struct ReplicatedBackend {
+    primary: Box<dyn Backend>,
+    replicas: Vec<Box<dyn Backend>>,
+    next_replica: AtomicUsize,
+}
+
+impl ReplicatedBackend {
+    /// Create a replicated backend; with no replicas every query goes to the primary
+    pub fn new(primary: Box<dyn Backend>, replicas: Vec<Box<dyn Backend>>) -> Self {
+        Self {
+            primary,
+            replicas,
+            next_replica: AtomicUsize::new(0),
+        }
+    }
+
+    /// Get the primary (write) backend
+    pub fn primary(&self) -> &dyn Backend {
+        self.primary.as_ref()
+    }
+
+    /// Get the replica (read) backends
+    pub fn replicas(&self) -> &[Box<dyn Backend>] {
+        &self.replicas
+    }
+
+    /// Pick the backend for the next read
+    fn reader(&self) -> &dyn Backend {
+        if self.replicas.is_empty() {
+            return self.primary.as_ref();
+        }
+        let index = self.next_replica.fetch_add(1, Ordering::Relaxed) % self.replicas.len();
+        self.replicas[index].as_ref()
+    }
+}
+
+#[async_trait]
+impl Backend for ReplicatedBackend {
+    fn name(&self) -> &str {
+        self.primary.name()
+    }
+
+    fn connection_url(&self) -> &str {
+        self.primary.connection_url()
+    }
+
+    fn query_builder(&self) -> QueryBuilderEnum {
+        self.primary.query_builder()
+    }
+
+    // Executors may write, so they are bound to the primary
+    fn executor(&self, sql: &str) -> QueryExecutor {
+        self.primary.executor(sql)
+    }
+
+    async fn execute_raw(&self, sql: &str) -> Result<u64> {
+        #[allow(deprecated)]
+        self.primary.execute_raw(sql).await
+    }
+
+    async fn execute(&self, sql: &str, params: &[QueryValue]) -> Result<u64> {
+        self.primary.execute(sql, params).await
+    }
+
+    async fn execute_returning(&self, sql: &str, params: &[QueryValue]) -> Result<Option<i64>> {
+        self.primary.execute_returning(sql, params).await
+    }
+
+    async fn execute_batch(&self, sql: &str) -> Result<u64> {
+        self.primary.execute_batch(sql).await
+    }
+
+    async fn fetch_all(&self, sql: &str) -> Result<Vec<serde_json::Value>> {
+        #[allow(deprecated)]
+        self.reader().fetch_all(sql).await
+    }
+
+    async fn fetch_all_params(&self, sql: &str, params: &[QueryValue]) -> Result<Vec<serde_json::Value>> {
+        self.reader().fetch_all_params(sql, params).await
+    }
+
+    async fn fetch_one(&self, sql: &str) -> Result<Option<serde_json::Value>> {
+        #[allow(deprecated)]
+        self.reader().fetch_one(sql).await
+    }
+
+    async fn fetch_one_params(&self, sql: &str, params: &[QueryValue]) -> Result<Option<serde_json::Value>> {
+        self.reader().fetch_one_params(sql, params).await
+    }
+
+    async fn explain(&self, sql: &str, params: &[QueryValue]) -> Result<Vec<serde_json::Value>> {
+        self.reader().explain(sql, params).await
+    }
+
+    // Replicas may lag behind schema changes, so ask the primary
+    async fn table_exists(&self, table: &str) -> Result<bool> {
+        self.primary.table_exists(table).await
+    }
+
+    async fn column_exists(&self, table: &str, column: &str) -> Result<bool> {
+        self.primary.column_exists(table, column).await
+    }
+
+    // Transactions hold a primary connection, so every query inside one sees its writes
+    async fn begin_transaction(&self) -> Result<crate::transaction::Transaction> {
+        self.primary.begin_transaction().await
+    }
+
+    async fn begin_read_transaction(&self) -> Result<crate::transaction::Transaction> {
+        self.reader().begin_read_transaction().await
+    }
+
+    async fn close(&self) {
+        self.primary.close().await;
+        for replica in &self.replicas {
+            replica.close().await;
+        }
+    }
+
+    fn is_closed(&self) -> bool {
+        self.primary.is_closed()
+    }
+
+    fn supports_feature(&self, feature: BackendFeature) -> bool {
+        self.primary.supports_feature(feature)
+    }
+}
@@ -1,21 +1,33 @@
-use crate::backend::{Backend, BackendFeature, GenericBackend};
+use crate::backend::{map_pool_error, Backend, BackendFeature, GenericBackend};
+use crate::connection::ConnectOptions;
 use crate::error::Result;
 use crate::query::builder::{Dialect, QueryBuilderEnum};
-use crate::query::QueryValue;
+use crate::query::{ExecutorPool, QueryExecutor, QueryValue};
 use async_trait::async_trait;
 use sqlx::SqlitePool;
-use sqlx::sqlite::SqliteConnectOptions;
-use std::str::FromStr;
 
 pub type SQLiteBackend = GenericBackend<SqlitePool>;
 
 impl SQLiteBackend {
     pub async fn connect(url: &str) -> Result<Self> {
-        // Create the database file if it doesn't exist
-        let options = SqliteConnectOptions::from_str(url)?
-            .create_if_missing(true);
-        
-        let pool = SqlitePool::connect_with(options).await?;
+        Self::connect_with(url, &ConnectOptions::default()).await
+    }
+
+    pub async fn connect_with(url: &str, options: &ConnectOptions) -> Result<Self> {
+        let sqlite_options = options.sqlite_options(url)?;
+
+        // A read-only transaction sets `PRAGMA query_only` on its connection; clear it whenever
+        // a connection comes back, and close the connection if that fails
+        let mut pool_options = options.pool_options().after_release(|conn, _| {
+            Box::pin(async move { Ok(sqlx::query("PRAGMA query_only = OFF").execute(conn).await.is_ok()) })
+        });
+        if is_in_memory(url) {
+            // sqlx gives each in-memory pool one shared-cache database, which SQLite drops
+            // with its last connection; keep one open so reaping idle connections can't wipe it
+            pool_options = pool_options.min_connections(1).idle_timeout(None).max_lifetime(None);
+        }
+
+        let pool = pool_options.connect_with(sqlite_options).await?;
         Ok(GenericBackend::new(
             pool,
             url.to_string(),
@@ -25,6 +37,11 @@ impl SQLiteBackend {
     }
 }
 
+/// Whether a SQLite URL names an in-memory database
+fn is_in_memory(url: &str) -> bool {
+    url.contains(":memory:") || url.contains("mode=memory")
+}
+
 #[async_trait]
 impl Backend for SQLiteBackend {
     fn name(&self) -> &str {
@@ -39,73 +56,95 @@ impl Backend for SQLiteBackend {
         QueryBuilderEnum::new(self.dialect)
     }
 
+    fn executor(&self, sql: &str) -> QueryExecutor {
+        QueryExecutor::with_pool(sql.to_string(), ExecutorPool::SQLite(self.pool().clone()))
+    }
+
     async fn execute_raw(&self, sql: &str) -> Result<u64> {
-        let result = sqlx::query(sql).execute(self.pool()).await?;
+        self.ensure_open()?;
+        let result = sqlx::query(sql).execute(self.pool()).await.map_err(map_pool_error(self.pool()))?;
         Ok(result.rows_affected())
     }
 
     async fn execute(&self, sql: &str, params: &[QueryValue]) -> Result<u64> {
-        let mut query = sqlx::query(sql);
-        for param in params {
-            query = match param {
-                QueryValue::Null => query.bind(Option::<i64>::None),
-                QueryValue::Bool(v) => query.bind(*v),
-                QueryValue::I32(v) => query.bind(*v),
-                QueryValue::I64(v) => query.bind(*v),
-                QueryValue::F64(v) => query.bind(*v),
-                QueryValue::String(v) => query.bind(v.as_str()),
-            };
-        }
-        let result = query.execute(self.pool()).await?;
+        self.ensure_open()?;
+        let query = crate::utils::bind_sqlite_params(sqlx::query(sql), params);
+        let result = query.execute(self.pool()).await.map_err(map_pool_error(self.pool()))?;
+        Ok(result.rows_affected())
+    }
+
+    async fn execute_returning(&self, sql: &str, params: &[QueryValue]) -> Result<Option<i64>> {
+        self.ensure_open()?;
+        let query = crate::utils::bind_sqlite_params(sqlx::query(sql), params);
+        let result = query.execute(self.pool()).await.map_err(map_pool_error(self.pool()))?;
+        Ok((result.rows_affected() > 0).then(|| result.last_insert_rowid()))
+    }
+
+    async fn execute_batch(&self, sql: &str) -> Result<u64> {
+        self.ensure_open()?;
+        let result = sqlx::raw_sql(sql).execute(self.pool()).await.map_err(map_pool_error(self.pool()))?;
         Ok(result.rows_affected())
     }
 
     async fn fetch_all(&self, sql: &str) -> Result<Vec<serde_json::Value>> {
-        let rows = sqlx::query(sql).fetch_all(self.pool()).await?;
+        self.ensure_open()?;
+        let rows = sqlx::query(sql).fetch_all(self.pool()).await.map_err(map_pool_error(self.pool()))?;
         Ok(rows.iter().map(crate::utils::sqlite_row_to_json).collect())
     }
 
     async fn fetch_all_params(&self, sql: &str, params: &[QueryValue]) -> Result<Vec<serde_json::Value>> {
-        let mut query = sqlx::query(sql);
-        for param in params {
-            query = match param {
-                QueryValue::Null => query.bind(Option::<i64>::None),
-                QueryValue::Bool(v) => query.bind(*v),
-                QueryValue::I32(v) => query.bind(*v),
-                QueryValue::I64(v) => query.bind(*v),
-                QueryValue::F64(v) => query.bind(*v),
-                QueryValue::String(v) => query.bind(v.as_str()),
-            };
-        }
-        let rows = query.fetch_all(self.pool()).await?;
+        self.ensure_open()?;
+        let query = crate::utils::bind_sqlite_params(sqlx::query(sql), params);
+        let rows = query.fetch_all(self.pool()).await.map_err(map_pool_error(self.pool()))?;
         Ok(rows.iter().map(crate::utils::sqlite_row_to_json).collect())
     }
 
     async fn fetch_one(&self, sql: &str) -> Result<Option<serde_json::Value>> {
-        let row_opt = sqlx::query(sql).fetch_optional(self.pool()).await?;
+        self.ensure_open()?;
+        let row_opt = sqlx::query(sql).fetch_optional(self.pool()).await.map_err(map_pool_error(self.pool()))?;
         Ok(row_opt.as_ref().map(crate::utils::sqlite_row_to_json))
     }
 
     async fn fetch_one_params(&self, sql: &str, params: &[QueryValue]) -> Result<Option<serde_json::Value>> {
-        let mut query = sqlx::query(sql);
-        for param in params {
-            query = match param {
-                QueryValue::Null => query.bind(Option::<i64>::None),
-                QueryValue::Bool(v) => query.bind(*v),
-                QueryValue::I32(v) => query.bind(*v),
-                QueryValue::I64(v) => query.bind(*v),
-                QueryValue::F64(v) => query.bind(*v),
-                QueryValue::String(v) => query.bind(v.as_str()),
-            };
-        }
-        let row_opt = query.fetch_optional(self.pool()).await?;
+        self.ensure_open()?;
+        let query = crate::utils::bind_sqlite_params(sqlx::query(sql), params);
+        let row_opt = query.fetch_optional(self.pool()).await.map_err(map_pool_error(self.pool()))?;
         Ok(row_opt.as_ref().map(crate::utils::sqlite_row_to_json))
     }
 
+    async fn explain(&self, sql: &str, params: &[QueryValue]) -> Result<Vec<serde_json::Value>> {
+        self.fetch_all_params(&format!("EXPLAIN QUERY PLAN {}", sql), params).await
+    }
+
+    async fn table_exists(&self, table: &str) -> Result<bool> {
+        let sql = "SELECT 1 FROM sqlite_master WHERE type IN ('table', 'view') AND name = ?";
+        Ok(self.fetch_one_params(sql, &[QueryValue::from(table)]).await?.is_some())
+    }
+
+    async fn column_exists(&self, table: &str, column: &str) -> Result<bool> {
+        let sql = "SELECT 1 FROM pragma_table_info(?) WHERE name = ?";
+        let params = [QueryValue::from(table), QueryValue::from(column)];
+        Ok(self.fetch_one_params(sql, &params).await?.is_some())
+    }
+
     async fn begin_transaction(&self) -> Result<crate::transaction::Transaction> {
+        self.ensure_open()?;
         crate::transaction::Transaction::new_sqlite(self.pool()).await
     }
 
+    async fn begin_read_transaction(&self) -> Result<crate::transaction::Transaction> {
+        self.ensure_open()?;
+        crate::transaction::Transaction::new_sqlite_read_only(self.pool()).await
+    }
+
+    async fn close(&self) {
+        self.pool().close().await;
+    }
+
+    fn is_closed(&self) -> bool {
+        self.pool().is_closed()
+    }
+
     fn supports_feature(&self, feature: BackendFeature) -> bool {
         match feature {
             BackendFeature::Transactions => true,
@@ -114,6 +153,7 @@ impl Backend for SQLiteBackend {
             BackendFeature::OnConflict => true,
             BackendFeature::CTE => true,
             BackendFeature::Window => true,
+            BackendFeature::FullJoin => true, // SQLite 3.39+, bundled by sqlx
         }
     }
 }
\ No newline at end of file
@@ -1,10 +1,19 @@
+pub mod options;
 pub mod pool;
 
+pub use options::{ConnectOptions, TlsMode};
+
 use crate::backend::{Backend, DatabaseBackend};
 use crate::backend::{mysql::MySQLBackend, sqlite::SQLiteBackend};
 use crate::error::Result;
 use crate::transaction::Transaction;
 use async_trait::async_trait;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+/// Delay before the first retry in `Database::transaction_retry`, doubled after each attempt
+const TRANSACTION_RETRY_BACKOFF: Duration = Duration::from_millis(10);
 
 #[async_trait]
 pub trait Connection: Send + Sync {
@@ -36,6 +45,45 @@ impl Database {
         Ok(Self { backend })
     }
 
+    /// Wrap an already-constructed backend (e.g. a `ReplicatedBackend`)
+    pub fn from_backend(backend: Box<dyn Backend>) -> Self {
+        Self { backend }
+    }
+
+    /// Connect to a database using a connection URL and connection options
+    pub async fn connect_with(url: &str, options: &ConnectOptions) -> Result<Self> {
+        let backend_type = DatabaseBackend::from_url(url)?;
+
+        let backend: Box<dyn Backend> = match backend_type {
+            DatabaseBackend::SQLite => Box::new(SQLiteBackend::connect_with(url, options).await?),
+            DatabaseBackend::MySQL => Box::new(MySQLBackend::connect_with(url, options).await?),
+        };
+
+        Ok(Self { backend })
+    }
+
+    /// Connect, retrying transient failures with exponential backoff
+    ///
+    /// Makes up to `attempts` tries, sleeping `backoff` after the first failure and
+    /// doubling it after each one. Permanent errors (bad URL, authentication) are
+    /// returned immediately; otherwise the last error is returned.
+    pub async fn connect_with_retry(url: &str, attempts: u32, backoff: Duration) -> Result<Self> {
+        let mut delay = backoff;
+        let mut attempt = 1;
+
+        loop {
+            match Self::connect(url).await {
+                Ok(db) => return Ok(db),
+                Err(e) if attempt < attempts && e.is_transient() => {
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
     /// Get a reference to the backend
     pub fn backend(&self) -> &dyn Backend {
         self.backend.as_ref()
@@ -46,8 +94,61 @@ impl Database {
         self.backend.execute(sql, &[]).await
     }
 
+    /// Close the connection pool, waiting for in-flight queries to finish
+    pub async fn close(self) {
+        self.backend.close().await;
+    }
+
     /// Begin a new transaction
     pub async fn begin_transaction(&self) -> Result<Transaction> {
         self.backend.begin_transaction().await
     }
+
+    /// Begin a read-only transaction, on a replica when the backend is replicated
+    pub async fn begin_read_transaction(&self) -> Result<Transaction> {
+        self.backend.begin_read_transaction().await
+    }
+
+    /// Run `f` in a transaction, retrying the whole transaction on serialization
+    /// failures and deadlocks
+    ///
+    /// Makes up to `max_attempts` tries, rolling back and backing off between them.
+    /// The closure is re-run from scratch on each attempt, so it must not have side
+    /// effects outside the transaction. Other errors are returned immediately.
+    ///
+    /// ```ignore
+    /// db.transaction_retry(3, |tx| Box::pin(async move {
+    ///     tx.execute_params("UPDATE accounts SET balance = balance - 10 WHERE id = ?", &[1.into()]).await
+    /// })).await?;
+    /// ```
+    pub async fn transaction_retry<T, F>(&self, max_attempts: u32, mut f: F) -> Result<T>
+    where
+        F: for<'t> FnMut(&'t mut Transaction) -> Pin<Box<dyn Future<Output = Result<T>> + Send + 't>>,
+    {
+        let mut delay = TRANSACTION_RETRY_BACKOFF;
+        let mut attempt = 1;
+
+        loop {
+            let mut tx = self.begin_transaction().await?;
+            let result = match f(&mut tx).await {
+                Ok(value) => tx.commit().await.map(|_| value),
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Ok(value) => return Ok(value),
+                Err(e) => {
+                    if tx.is_open() {
+                        let _ = tx.rollback().await;
+                    }
+                    if attempt >= max_attempts || !e.is_retryable_transaction_error() {
+                        return Err(e);
+                    }
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
+                    attempt += 1;
+                }
+            }
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,119 @@
+use crate::error::{Error, Result};
+use sqlx::mysql::{MySqlConnectOptions, MySqlSslMode};
+use sqlx::pool::PoolOptions;
+use sqlx::sqlite::SqliteConnectOptions;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Options applied when opening a database connection
+#[derive(Debug, Clone, Default)]
+pub struct ConnectOptions {
+    /// TLS requirement for MySQL connections; `None` keeps the URL's `ssl-mode` or the driver default
+    pub tls: Option<TlsMode>,
+    /// Maximum pooled connections; `None` keeps the driver default
+    pub max_connections: Option<u32>,
+    /// How long to wait for a free pooled connection before `Error::PoolTimeout`
+    pub acquire_timeout: Option<Duration>,
+    /// Prepared statements kept per connection, keyed by SQL; `0` disables the cache
+    /// and `None` keeps the driver default (100)
+    pub statement_cache_capacity: Option<usize>,
+    /// How long a SQLite statement waits on a locked database before failing with
+    /// `SQLITE_BUSY`; `None` keeps the driver default (5s)
+    pub busy_timeout: Option<Duration>,
+}
+
+/// Whether a connection must, may, or must not use TLS
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TlsMode {
+    Disabled,
+    Preferred,
+    Required,
+}
+
+impl ConnectOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the TLS requirement
+    pub fn tls(mut self, mode: TlsMode) -> Self {
+        self.tls = Some(mode);
+        self
+    }
+
+    /// Set the maximum number of pooled connections
+    pub fn max_connections(mut self, max: u32) -> Self {
+        self.max_connections = Some(max);
+        self
+    }
+
+    /// Set how long to wait for a pooled connection
+    pub fn acquire_timeout(mut self, timeout: Duration) -> Self {
+        self.acquire_timeout = Some(timeout);
+        self
+    }
+
+    /// Set how many prepared statements each connection caches
+    pub fn statement_cache_capacity(mut self, capacity: usize) -> Self {
+        self.statement_cache_capacity = Some(capacity);
+        self
+    }
+
+    /// Set how long SQLite waits for a lock held by another connection
+    pub fn busy_timeout(mut self, timeout: Duration) -> Self {
+        self.busy_timeout = Some(timeout);
+        self
+    }
+
+    /// Build sqlx pool options with these options applied
+    pub(crate) fn pool_options<DB: sqlx::Database>(&self) -> PoolOptions<DB> {
+        let mut pool = PoolOptions::new();
+        if let Some(max) = self.max_connections {
+            pool = pool.max_connections(max);
+        }
+        if let Some(timeout) = self.acquire_timeout {
+            pool = pool.acquire_timeout(timeout);
+        }
+        pool
+    }
+
+    /// Build sqlx SQLite options from a URL with these options applied, creating the
+    /// database file if it doesn't exist
+    pub fn sqlite_options(&self, url: &str) -> Result<SqliteConnectOptions> {
+        let mut options = SqliteConnectOptions::from_str(url)?.create_if_missing(true);
+
+        if let Some(capacity) = self.statement_cache_capacity {
+            options = options.statement_cache_capacity(capacity);
+        }
+        if let Some(timeout) = self.busy_timeout {
+            options = options.busy_timeout(timeout);
+        }
+
+        Ok(options)
+    }
+
+    /// Build sqlx MySQL options from a URL with these options applied
+    pub fn mysql_options(&self, url: &str) -> Result<MySqlConnectOptions> {
+        let mut options = MySqlConnectOptions::from_str(url)?;
+
+        if let Some(capacity) = self.statement_cache_capacity {
+            options = options.statement_cache_capacity(capacity);
+        }
+
+        if let Some(mode) = self.tls {
+            if mode != TlsMode::Disabled && !cfg!(feature = "native-tls") {
+                return Err(Error::ConfigError(
+                    "TLS connections require the `native-tls` feature".to_string(),
+                ));
+            }
+
+            options = options.ssl_mode(match mode {
+                TlsMode::Disabled => MySqlSslMode::Disabled,
+                TlsMode::Preferred => MySqlSslMode::Preferred,
+                TlsMode::Required => MySqlSslMode::Required,
+            });
+        }
+
+        Ok(options)
+    }
+}
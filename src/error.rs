@@ -1,3 +1,4 @@
+use sqlx::error::ErrorKind;
 use thiserror::Error;
 
 /// Core error type for the ORM
@@ -12,23 +13,126 @@ pub enum Error {
     #[error("Transaction error: {0}")]
     TransactionError(String),
 
+    #[error("Transaction already completed")]
+    TransactionClosed,
+
     #[error("Migration error: {0}")]
     MigrationError(String),
 
     #[error("Serialization error: {0}")]
     SerializationError(String),
 
-    #[error("Constraint violation: {0}")]
-    ConstraintViolation(String),
+    /// A write rejected by a UNIQUE, FOREIGN KEY, NOT NULL or CHECK constraint
+    ///
+    /// `constraint` is the constraint name where the driver reports it (Postgres does;
+    /// SQLite and MySQL only include it in `message`).
+    #[error("Constraint violation: {message}")]
+    ConstraintViolation {
+        kind: ConstraintKind,
+        constraint: Option<String>,
+        message: String,
+    },
+
+    #[error("Validation failed: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
+    Validation(Vec<crate::model::ValidationError>),
+
+    #[error("Timed out after {0:?} waiting for a connection from the pool")]
+    PoolTimeout(std::time::Duration),
 
     #[error("Invalid configuration: {0}")]
     ConfigError(String),
 
+    /// Any driver error not classified into a more specific variant
     #[error("Database error: {0}")]
-    DatabaseError(#[from] sqlx::Error),
+    DatabaseError(sqlx::Error),
 
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 }
 
+/// The kind of constraint behind an `Error::ConstraintViolation`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstraintKind {
+    Unique,
+    ForeignKey,
+    NotNull,
+    Check,
+}
+
+impl Error {
+    /// Whether the error is likely temporary (server not up yet, pool exhausted)
+    /// rather than permanent (bad credentials, invalid configuration)
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Error::ConnectionError(_) | Error::PoolTimeout(_) | Error::IoError(_) => true,
+            Error::DatabaseError(e) => match e {
+                sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut => true,
+                // SQLITE_BUSY, SQLITE_CANTOPEN (e.g. volume not mounted yet) and their
+                // extended codes, MySQL ER_CON_COUNT_ERROR (too many connections)
+                sqlx::Error::Database(db) => {
+                    matches!(sqlite_primary_code(db.as_ref()), Some(5 | 14))
+                        || matches!(db.code().as_deref(), Some("1040"))
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Whether a transaction failed because of a conflict with a concurrent one
+    /// (serialization failure, deadlock, locked database) and can be retried as a whole
+    pub fn is_retryable_transaction_error(&self) -> bool {
+        match self {
+            // SQLSTATE 40001 (MySQL/Postgres serialization failure or deadlock),
+            // 40P01 (Postgres deadlock), SQLITE_BUSY and SQLITE_LOCKED including their
+            // extended codes (e.g. SQLITE_BUSY_SNAPSHOT for a WAL read-to-write upgrade)
+            Error::DatabaseError(sqlx::Error::Database(db)) => {
+                matches!(sqlite_primary_code(db.as_ref()), Some(5 | 6))
+                    || matches!(db.code().as_deref(), Some("40001" | "40P01"))
+            }
+            _ => false,
+        }
+    }
+}
+
+/// The primary result code of a SQLite error
+///
+/// sqlx reports SQLite's extended result code (`SQLITE_BUSY_SNAPSHOT` is 517); its
+/// low byte is the primary code (`SQLITE_BUSY`, 5).
+fn sqlite_primary_code(db: &dyn sqlx::error::DatabaseError) -> Option<i32> {
+    db.try_downcast_ref::<sqlx::sqlite::SqliteError>()?;
+    db.code()?.parse::<i32>().ok().map(|code| code & 0xff)
+}
+
+/// Classify a driver error: crashed workers become `ConnectionError`, row decode
+/// failures `SerializationError`, constraint violations `ConstraintViolation`, and
+/// everything else `DatabaseError`
+///
+/// A closed pool stays `DatabaseError`, which is not transient: it was closed on
+/// purpose and never comes back. Acquire timeouts also stay `DatabaseError` here;
+/// pool operations report them as `PoolTimeout` with the pool's configured timeout.
+impl From<sqlx::Error> for Error {
+    fn from(e: sqlx::Error) -> Self {
+        match e {
+            sqlx::Error::WorkerCrashed => Error::ConnectionError(e.to_string()),
+            sqlx::Error::Decode(_) | sqlx::Error::ColumnDecode { .. } => Error::SerializationError(e.to_string()),
+            sqlx::Error::Database(db) => {
+                let kind = match db.kind() {
+                    ErrorKind::UniqueViolation => ConstraintKind::Unique,
+                    ErrorKind::ForeignKeyViolation => ConstraintKind::ForeignKey,
+                    ErrorKind::NotNullViolation => ConstraintKind::NotNull,
+                    ErrorKind::CheckViolation => ConstraintKind::Check,
+                    _ => return Error::DatabaseError(sqlx::Error::Database(db)),
+                };
+                Error::ConstraintViolation {
+                    kind,
+                    constraint: db.constraint().map(str::to_string),
+                    message: db.message().to_string(),
+                }
+            }
+            e => Error::DatabaseError(e),
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, Error>;
\ No newline at end of file
@@ -8,13 +8,13 @@ pub mod schema;
 pub mod transaction;
 pub mod utils;
 
-pub use error::{Error, Result};
+pub use error::{ConstraintKind, Error, Result};
 
 pub mod prelude {
     pub use crate::backend::{Backend, DatabaseBackend};
     pub use crate::connection::{Connection, Database};
-    pub use crate::error::{Error, Result};
-    pub use crate::model::{FromRow, Model, ModelCrud, ModelQuery, Value};
+    pub use crate::error::{ConstraintKind, Error, Result};
+    pub use crate::model::{FromRow, FromValue, Model, ModelCrud, ModelQuery, Page, RowExt, ToValue, Validate, ValidationError, Value};
     pub use crate::query::{JoinType, OrderDirection, QueryBuilder};
     pub use crate::schema::{Column, Table, SchemaExport, export_schema, export_schema_json};
     pub use crate::transaction::Transaction;
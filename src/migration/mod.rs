@@ -1,7 +1,9 @@
 use crate::backend::Backend;
-use crate::error::Result;
-use crate::query::builder::Dialect;
-use crate::schema::{Column, ColumnType, ForeignKey, Table};
+use crate::error::{Error, Result};
+use crate::query::builder::{Dialect, QueryBuilderEnum};
+use crate::query::{QueryBuilder, QueryValue};
+use crate::transaction::Transaction;
+use crate::schema::{Column, ColumnType, ForeignKey, ForeignKeyAction, SchemaDiff, Table};
 use async_trait::async_trait;
 
 /// Represents a database migration
@@ -21,19 +23,25 @@ pub trait Migration: Send + Sync {
 }
 
 /// Schema builder for migrations
+#[derive(Debug, Clone)]
 pub struct Schema {
     dialect: Dialect,
     operations: Vec<SchemaOperation>,
 }
 
-#[derive(Debug)]
-enum SchemaOperation {
+/// A pending schema change queued on a `Schema`
+#[derive(Debug, Clone)]
+pub enum SchemaOperation {
     CreateTable(Table),
     DropTable(String),
+    DropTableCascade(String),
     AddColumn { table: String, column: Column },
     DropColumn { table: String, column: String },
+    ModifyColumn { table: String, column: Column },
     CreateIndex { table: String, name: String, columns: Vec<String>, unique: bool },
     DropIndex { name: String },
+    Seed { table: String, columns: Vec<String>, rows: Vec<Vec<QueryValue>> },
+    Raw(String),
 }
 
 impl Schema {
@@ -44,6 +52,11 @@ impl Schema {
         }
     }
 
+    /// The operations queued so far, in execution order
+    pub fn operations(&self) -> &[SchemaOperation] {
+        &self.operations
+    }
+
     /// Create a new table
     pub fn create_table<F>(&mut self, name: impl Into<String>, builder: F) -> &mut Self
     where
@@ -61,6 +74,19 @@ impl Schema {
         self
     }
 
+    /// Drop a table even if other tables have foreign keys referencing it
+    ///
+    /// Postgres appends `CASCADE`, which also drops those constraints. MySQL turns
+    /// `FOREIGN_KEY_CHECKS` off around the drop and leaves the referencing
+    /// constraints dangling. SQLite has no cascade and can't change `PRAGMA
+    /// foreign_keys` inside the migration's transaction; a referenced table with
+    /// rows still pointing at it must be dropped with foreign keys disabled on the
+    /// connection beforehand.
+    pub fn drop_table_cascade(&mut self, name: impl Into<String>) -> &mut Self {
+        self.operations.push(SchemaOperation::DropTableCascade(name.into()));
+        self
+    }
+
     /// Add a column to an existing table
     pub fn add_column(&mut self, table: impl Into<String>, column: Column) -> &mut Self {
         self.operations.push(SchemaOperation::AddColumn {
@@ -79,6 +105,43 @@ impl Schema {
         self
     }
 
+    /// Change the definition of an existing column
+    pub fn modify_column(&mut self, table: impl Into<String>, column: Column) -> &mut Self {
+        self.operations.push(SchemaOperation::ModifyColumn {
+            table: table.into(),
+            column,
+        });
+        self
+    }
+
+    /// Queue the operations needed to apply a schema diff.
+    ///
+    /// Foreign key changes on existing tables are not translated, as they
+    /// cannot be altered in place on every dialect.
+    pub fn apply_diff(&mut self, diff: &SchemaDiff) -> &mut Self {
+        for table in &diff.added_tables {
+            self.operations.push(SchemaOperation::CreateTable(table.to_table()));
+        }
+
+        for table in &diff.changed_tables {
+            for column in &table.added_columns {
+                self.add_column(&table.name, column.to_column());
+            }
+            for change in &table.changed_columns {
+                self.modify_column(&table.name, change.to.to_column());
+            }
+            for column in &table.removed_columns {
+                self.drop_column(&table.name, column);
+            }
+        }
+
+        for name in &diff.removed_tables {
+            self.drop_table(name);
+        }
+
+        self
+    }
+
     /// Create an index
     pub fn create_index(
         &mut self,
@@ -104,26 +167,126 @@ impl Schema {
         self
     }
 
-    /// Execute all schema operations
+    /// Insert rows into a table, e.g. reference data for a table created earlier in
+    /// the same migration. Values are bound as parameters.
+    pub fn seed(&mut self, table: impl Into<String>, columns: &[&str], rows: Vec<Vec<QueryValue>>) -> &mut Self {
+        if rows.is_empty() {
+            return self;
+        }
+        self.operations.push(SchemaOperation::Seed {
+            table: table.into(),
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            rows,
+        });
+        self
+    }
+
+    /// Run a single hand-written statement (a trigger, view, generated column...)
+    /// verbatim, in order with the other operations
+    pub fn raw(&mut self, sql: impl Into<String>) -> &mut Self {
+        self.operations.push(SchemaOperation::Raw(sql.into()));
+        self
+    }
+
+    /// Execute all schema operations in a single transaction.
+    ///
+    /// MySQL commits implicitly after each DDL statement, so only SQLite and
+    /// Postgres roll back schema changes if a later operation fails.
     pub async fn execute(&self, backend: &dyn Backend) -> Result<()> {
-        for operation in &self.operations {
-            let sql = self.operation_to_sql(operation);
-            backend.execute(&sql, &[]).await?;
+        let mut tx = backend.begin_transaction().await?;
+        self.execute_in(&mut tx).await?;
+        tx.commit().await
+    }
+
+    /// Execute all schema operations inside an existing transaction
+    pub async fn execute_in(&self, tx: &mut Transaction) -> Result<()> {
+        // Render everything first so an invalid operation fails before any SQL runs
+        let statements = self
+            .operations
+            .iter()
+            .map(|operation| Ok((operation, self.operation_to_sql(operation)?)))
+            .collect::<Result<Vec<_>>>()?;
+
+        for (operation, statements) in &statements {
+            match operation {
+                SchemaOperation::DropTableCascade(name) if matches!(self.dialect, Dialect::MySQL) => {
+                    drop_without_foreign_key_checks(tx, name).await?;
+                }
+                _ => {
+                    for (sql, params) in statements {
+                        tx.execute_params(sql, params).await?;
+                    }
+                }
+            }
         }
-        
         Ok(())
     }
 
-    fn operation_to_sql(&self, operation: &SchemaOperation) -> String {
-        match operation {
+    /// Render all schema operations to SQL without executing them
+    pub fn to_sql(&self) -> Result<Vec<String>> {
+        Ok(self.to_statements()?.into_iter().map(|(sql, _)| sql).collect())
+    }
+
+    fn to_statements(&self) -> Result<Vec<(String, Vec<QueryValue>)>> {
+        let mut statements = Vec::new();
+        for operation in &self.operations {
+            statements.extend(self.operation_to_sql(operation)?);
+        }
+        Ok(statements)
+    }
+
+    fn operation_to_sql(&self, operation: &SchemaOperation) -> Result<Vec<(String, Vec<QueryValue>)>> {
+        let sql = match operation {
             SchemaOperation::CreateTable(table) => table.to_create_sql(self.dialect),
             SchemaOperation::DropTable(name) => format!("DROP TABLE IF EXISTS {}", name),
+            SchemaOperation::DropTableCascade(name) => match self.dialect {
+                Dialect::SQLite => format!("DROP TABLE IF EXISTS {}", name),
+                Dialect::MySQL => {
+                    return Ok([
+                        "SET FOREIGN_KEY_CHECKS = 0".to_string(),
+                        format!("DROP TABLE IF EXISTS {}", name),
+                        "SET FOREIGN_KEY_CHECKS = 1".to_string(),
+                    ]
+                    .into_iter()
+                    .map(|sql| (sql, Vec::new()))
+                    .collect());
+                }
+                Dialect::Postgres => format!("DROP TABLE IF EXISTS {} CASCADE", name),
+            },
             SchemaOperation::AddColumn { table, column } => {
                 format!("ALTER TABLE {} ADD COLUMN {}", table, column.to_sql(self.dialect))
             }
             SchemaOperation::DropColumn { table, column } => {
                 format!("ALTER TABLE {} DROP COLUMN {}", table, column)
             }
+            SchemaOperation::ModifyColumn { table, column } => match self.dialect {
+                Dialect::SQLite => {
+                    return Err(Error::MigrationError(format!(
+                        "SQLite does not support modifying column {} on {}",
+                        column.name(),
+                        table
+                    )));
+                }
+                Dialect::MySQL => {
+                    format!("ALTER TABLE {} MODIFY COLUMN {}", table, column.to_sql(self.dialect))
+                }
+                // Postgres alters one attribute per action; apply the same nullability and
+                // default as MySQL's full MODIFY COLUMN definition
+                Dialect::Postgres => {
+                    let name = column.name();
+                    let mut actions = vec![format!("ALTER COLUMN {} TYPE {}", name, column.type_to_sql(self.dialect))];
+                    // A primary key column is implicitly NOT NULL and can't drop it
+                    if !column.is_primary_key() {
+                        let nullability = if column.is_nullable() { "DROP NOT NULL" } else { "SET NOT NULL" };
+                        actions.push(format!("ALTER COLUMN {} {}", name, nullability));
+                    }
+                    actions.push(match column.default_value() {
+                        Some(default) => format!("ALTER COLUMN {} SET DEFAULT {}", name, default),
+                        None => format!("ALTER COLUMN {} DROP DEFAULT", name),
+                    });
+                    format!("ALTER TABLE {} {}", table, actions.join(", "))
+                }
+            },
             SchemaOperation::CreateIndex { table, name, columns, unique } => {
                 let unique_str = if *unique { "UNIQUE " } else { "" };
                 format!(
@@ -137,7 +300,28 @@ impl Schema {
             SchemaOperation::DropIndex { name } => {
                 format!("DROP INDEX IF EXISTS {}", name)
             }
-        }
+            SchemaOperation::Seed { table, columns, rows } => {
+                if let Some(row) = rows.iter().find(|row| row.len() != columns.len()) {
+                    return Err(Error::MigrationError(format!(
+                        "Seed row for {} has {} values but {} columns",
+                        table,
+                        row.len(),
+                        columns.len()
+                    )));
+                }
+
+                let columns: Vec<&str> = columns.iter().map(String::as_str).collect();
+                let mut builder = QueryBuilderEnum::new(self.dialect);
+                builder.insert_into(table, &columns);
+                for row in rows {
+                    builder.values_params(row);
+                }
+                return Ok(vec![builder.build_with_params()?]);
+            }
+            SchemaOperation::Raw(sql) => sql.clone(),
+        };
+
+        Ok(vec![(sql, Vec::new())])
     }
 }
 
@@ -176,6 +360,20 @@ impl TableBuilder {
         self
     }
 
+    /// Add a one-byte integer column (`SMALLINT` on Postgres)
+    pub fn tiny_integer(&mut self, name: impl Into<String>) -> &mut Self {
+        let column = Column::new(name, ColumnType::TinyInt);
+        self.table.add_column(column);
+        self
+    }
+
+    /// Add a two-byte integer column
+    pub fn small_integer(&mut self, name: impl Into<String>) -> &mut Self {
+        let column = Column::new(name, ColumnType::SmallInt);
+        self.table.add_column(column);
+        self
+    }
+
     /// Add an integer column
     pub fn integer(&mut self, name: impl Into<String>) -> &mut Self {
         let column = Column::new(name, ColumnType::Integer);
@@ -253,6 +451,13 @@ impl TableBuilder {
         self
     }
 
+    /// Add a binary column (`BLOB`, or `BYTEA` on Postgres)
+    pub fn binary(&mut self, name: impl Into<String>) -> &mut Self {
+        let column = Column::new(name, ColumnType::Binary);
+        self.table.add_column(column);
+        self
+    }
+
     /// Add timestamps (created_at, updated_at)
     pub fn timestamps(&mut self) -> &mut Self {
         self.timestamp("created_at");
@@ -266,6 +471,41 @@ impl TableBuilder {
         self
     }
 
+    /// Add a foreign key from `column` to `references_table(references_column)`,
+    /// returning a builder to set its name and ON DELETE/ON UPDATE actions
+    ///
+    /// The constraint is named `fk_<table>_<column>` unless renamed with `ForeignKeyBuilder::name`.
+    pub fn foreign_key_to(
+        &mut self,
+        column: impl Into<String>,
+        references_table: impl Into<String>,
+        references_column: impl Into<String>,
+    ) -> ForeignKeyBuilder<'_> {
+        let column = column.into();
+        let name = format!("fk_{}_{}", self.table.name(), column);
+        let fk = self.table.push_foreign_key(ForeignKey {
+            name: Some(name),
+            column,
+            references_table: references_table.into(),
+            references_column: references_column.into(),
+            on_delete: None,
+            on_update: None,
+        });
+        ForeignKeyBuilder { fk }
+    }
+
+    /// Add a named UNIQUE constraint spanning the given columns
+    pub fn unique(&mut self, name: impl Into<String>, columns: Vec<String>) -> &mut Self {
+        self.table.add_unique(name, columns);
+        self
+    }
+
+    /// Add a named CHECK constraint
+    pub fn check(&mut self, name: impl Into<String>, expression: impl Into<String>) -> &mut Self {
+        self.table.add_check(name, expression);
+        self
+    }
+
     /// Add an index
     pub fn index(&mut self, name: impl Into<String>, columns: Vec<String>, unique: bool) -> &mut Self {
         self.table.add_index(name, columns, unique);
@@ -277,6 +517,48 @@ impl TableBuilder {
     }
 }
 
+/// Drop a MySQL table with `FOREIGN_KEY_CHECKS` off, turning checks back on even if
+/// the drop fails
+///
+/// The setting is per session and isn't rolled back, so leaving it off would return
+/// the connection to the pool with foreign key checks disabled.
+async fn drop_without_foreign_key_checks(tx: &mut Transaction, name: &str) -> Result<()> {
+    tx.execute_params("SET FOREIGN_KEY_CHECKS = 0", &[]).await?;
+    let dropped = tx.execute_params(&format!("DROP TABLE IF EXISTS {}", name), &[]).await;
+    let restored = tx.execute_params("SET FOREIGN_KEY_CHECKS = 1", &[]).await;
+    dropped?;
+    restored?;
+    Ok(())
+}
+
+/// Fluent builder for a foreign key added with `TableBuilder::foreign_key_to`
+pub struct ForeignKeyBuilder<'a> {
+    fk: &'a mut ForeignKey,
+}
+
+impl ForeignKeyBuilder<'_> {
+    /// Set the constraint name
+    pub fn name(self, name: impl Into<String>) -> Self {
+        self.fk.name = Some(name.into());
+        self
+    }
+
+    /// Set the ON DELETE action
+    pub fn on_delete(self, action: ForeignKeyAction) -> Self {
+        self.fk.on_delete = Some(action);
+        self
+    }
+
+    /// Set the ON UPDATE action
+    pub fn on_update(self, action: ForeignKeyAction) -> Self {
+        self.fk.on_update = Some(action);
+        self
+    }
+}
+
+const MIGRATION_LOCK_NAME: &str = "orm_migrations";
+const MIGRATION_LOCK_TIMEOUT_SECS: i32 = 60;
+
 /// Migration runner
 pub struct MigrationRunner {
     dialect: Dialect,
@@ -315,9 +597,31 @@ impl MigrationRunner {
                     executed_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
                 )"
             }
+            Dialect::Postgres => {
+                "CREATE TABLE IF NOT EXISTS migrations (
+                    id BIGSERIAL PRIMARY KEY,
+                    version BIGINT NOT NULL UNIQUE,
+                    name VARCHAR(255) NOT NULL,
+                    executed_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+                )"
+            }
         };
         
         backend.execute(sql, &[]).await?;
+
+        // SQLite has no advisory locks; writing this row takes the database write lock
+        if self.dialect == Dialect::SQLite {
+            backend
+                .execute(
+                    "CREATE TABLE IF NOT EXISTS migrations_lock (
+                        id INTEGER PRIMARY KEY,
+                        locked_at TEXT NOT NULL
+                    )",
+                    &[],
+                )
+                .await?;
+        }
+
         Ok(())
     }
 
@@ -326,18 +630,104 @@ impl MigrationRunner {
         
         let rows = backend.fetch_all_params("SELECT version FROM migrations ORDER BY version", &[]).await?;
         
-        let versions = rows
+        Ok(Self::versions_from_rows(&rows))
+    }
+
+    /// Every applied migration as `(version, name, executed_at)`, oldest version first
+    pub async fn applied_migrations(&self, backend: &dyn Backend) -> Result<Vec<(i64, String, String)>> {
+        self.ensure_migrations_table(backend).await?;
+
+        let executed_at = match self.dialect {
+            Dialect::SQLite => "executed_at",
+            Dialect::MySQL => "CAST(executed_at AS CHAR) AS executed_at",
+            Dialect::Postgres => "CAST(executed_at AS TEXT) AS executed_at",
+        };
+        let sql = format!("SELECT version, name, {} FROM migrations ORDER BY version", executed_at);
+        let rows = backend.fetch_all_params(&sql, &[]).await?;
+
+        rows.iter()
+            .map(|row| {
+                let version = row.get("version").and_then(|v| v.as_i64());
+                let name = row.get("name").and_then(|v| v.as_str());
+                let executed_at = row.get("executed_at").and_then(|v| v.as_str());
+                match (version, name, executed_at) {
+                    (Some(version), Some(name), Some(executed_at)) => {
+                        Ok((version, name.to_string(), executed_at.to_string()))
+                    }
+                    _ => Err(Error::MigrationError(format!("Invalid migrations table row: {}", row))),
+                }
+            })
+            .collect()
+    }
+
+    fn versions_from_rows(rows: &[serde_json::Value]) -> Vec<i64> {
+        rows
             .iter()
             .filter_map(|row| row.get("version").and_then(|v| v.as_i64()))
-            .collect();
-        
-        Ok(versions)
+            .collect()
+    }
+
+    /// Take the migration lock on the transaction's connection, waiting for
+    /// any other runner to finish first
+    async fn acquire_lock(&self, tx: &mut Transaction) -> Result<()> {
+        match self.dialect {
+            Dialect::SQLite => {
+                let sql = "INSERT OR REPLACE INTO migrations_lock (id, locked_at) VALUES (1, datetime('now'))";
+                tx.execute_params(sql, &[]).await?;
+            }
+            Dialect::MySQL => {
+                let params = [QueryValue::from(MIGRATION_LOCK_NAME), QueryValue::I32(MIGRATION_LOCK_TIMEOUT_SECS)];
+                let row = tx.fetch_one_params("SELECT GET_LOCK(?, ?) AS acquired", &params).await?;
+                let acquired = row.and_then(|r| r.get("acquired").and_then(|v| v.as_i64()));
+                if acquired != Some(1) {
+                    return Err(Error::MigrationError(format!(
+                        "Timed out after {}s waiting for the migration lock",
+                        MIGRATION_LOCK_TIMEOUT_SECS
+                    )));
+                }
+            }
+            Dialect::Postgres => {
+                let params = [QueryValue::from(MIGRATION_LOCK_NAME)];
+                tx.fetch_one_params("SELECT pg_advisory_xact_lock(hashtext($1))", &params).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Release the migration lock. Only MySQL needs this, as its lock belongs to
+    /// the pooled connection rather than the transaction.
+    async fn release_lock(&self, tx: &mut Transaction) -> Result<()> {
+        if self.dialect == Dialect::MySQL {
+            tx.fetch_one_params("SELECT RELEASE_LOCK(?)", &[QueryValue::from(MIGRATION_LOCK_NAME)]).await?;
+        }
+        Ok(())
     }
 
-    /// Run all pending migrations
+    /// Run all pending migrations.
+    ///
+    /// Runners hold a database lock while applying migrations, so concurrent
+    /// runners against the same database apply each migration exactly once.
     pub async fn run_pending(&self, backend: &dyn Backend) -> Result<()> {
         self.ensure_migrations_table(backend).await?;
-        let executed = self.get_executed_versions(backend).await?;
+
+        let mut tx = backend.begin_transaction().await?;
+        self.acquire_lock(&mut tx).await?;
+        let result = self.run_pending_locked(backend, &mut tx).await;
+        let released = self.release_lock(&mut tx).await;
+
+        match result.and(released) {
+            Ok(()) => tx.commit().await,
+            Err(e) => {
+                tx.rollback().await.ok();
+                Err(e)
+            }
+        }
+    }
+
+    async fn run_pending_locked(&self, backend: &dyn Backend, tx: &mut Transaction) -> Result<()> {
+        // Read inside the lock so migrations applied by another runner are seen
+        let rows = tx.fetch_all_params("SELECT version FROM migrations ORDER BY version", &[]).await?;
+        let executed = Self::versions_from_rows(&rows);
         
         for migration in &self.migrations {
             if !executed.contains(&migration.version()) {
@@ -345,18 +735,19 @@ impl MigrationRunner {
                 
                 let mut schema = Schema::new(backend, self.dialect);
                 migration.up(&mut schema).await?;
-                schema.execute(backend).await?;
+                schema.execute_in(tx).await?;
                 
                 // Record migration with parameterized query
                 let sql = match self.dialect {
                     Dialect::SQLite => "INSERT INTO migrations (version, name, executed_at) VALUES (?, ?, datetime('now'))",
                     Dialect::MySQL => "INSERT INTO migrations (version, name, executed_at) VALUES (?, ?, NOW())",
+                    Dialect::Postgres => "INSERT INTO migrations (version, name, executed_at) VALUES ($1, $2, NOW())",
                 };
                 let params = vec![
-                    crate::query::QueryValue::I64(migration.version()),
-                    crate::query::QueryValue::String(migration.name().to_string()),
+                    QueryValue::I64(migration.version()),
+                    QueryValue::String(migration.name().to_string()),
                 ];
-                backend.execute(sql, &params).await?;
+                tx.execute_params(sql, &params).await?;
                 
                 println!("✓ Migration completed: {}", migration.name());
             }
@@ -380,9 +771,9 @@ impl MigrationRunner {
                 schema.execute(backend).await?;
                 
                 // Remove migration record with parameterized query
-                let sql = "DELETE FROM migrations WHERE version = ?";
+                let sql = format!("DELETE FROM migrations WHERE version = {}", self.dialect.placeholder(1));
                 let params = vec![crate::query::QueryValue::I64(version)];
-                backend.execute(sql, &params).await?;
+                backend.execute(&sql, &params).await?;
                 
                 println!("✓ Rollback completed: {}", migration.name());
             }
@@ -390,4 +781,21 @@ impl MigrationRunner {
         
         Ok(())
     }
+
+    /// Rollback every executed migration, newest first
+    pub async fn rollback_all(&self, backend: &dyn Backend) -> Result<()> {
+        self.ensure_migrations_table(backend).await?;
+        let executed = self.get_executed_versions(backend).await?;
+        self.rollback(backend, executed.len()).await
+    }
+
+    /// Rebuild the schema from scratch by rolling back every migration and
+    /// running them all again.
+    ///
+    /// This is destructive: every `down` migration runs, so all data in the
+    /// affected tables is lost. Intended for development and test databases.
+    pub async fn reset(&self, backend: &dyn Backend) -> Result<()> {
+        self.rollback_all(backend).await?;
+        self.run_pending(backend).await
+    }
 }
\ No newline at end of file
@@ -1,15 +1,17 @@
 use crate::backend::Backend;
 use crate::error::{Error, Result};
-use crate::model::{FromRow, Model, Value};
+use crate::model::{FromRow, Model, Validate, Value};
 use crate::query::builder::QueryBuilderEnum;
 use crate::query::{JoinType, OrderDirection, QueryBuilder};
 use crate::schema::{Column, ColumnType};
 use async_trait::async_trait;
+use std::collections::HashMap;
 
 /// Query builder helper for models
 pub struct ModelQuery<'a, T: Model> {
     builder: QueryBuilderEnum,
     backend: &'a dyn Backend,
+    count_aliases: Vec<String>,
     _phantom: std::marker::PhantomData<T>,
 }
 
@@ -20,10 +22,51 @@ impl<'a, T: Model + FromRow> ModelQuery<'a, T> {
         Self {
             builder,
             backend,
+            count_aliases: Vec::new(),
             _phantom: std::marker::PhantomData,
         }
     }
 
+    /// Run a hand-written statement and hydrate each row as a model
+    ///
+    /// For queries the builder can't express; the statement must select the model's columns.
+    pub async fn from_sql(backend: &'a dyn Backend, sql: &str, params: &[crate::query::QueryValue]) -> Result<Vec<T>> {
+        let json_rows = backend.fetch_all_params(sql, params).await?;
+
+        json_rows
+            .iter()
+            .map(|json| T::from_json(json))
+            .collect()
+    }
+
+    /// Snapshot this query so it can be branched with different clauses
+    pub fn clone_query(&self) -> Self {
+        Self {
+            builder: self.builder.clone(),
+            backend: self.backend,
+            count_aliases: self.count_aliases.clone(),
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// Query a different table than the model's default (e.g. a tenant-prefixed table)
+    pub fn table(mut self, name: &str) -> Self {
+        self.builder.from(name);
+        self
+    }
+
+    /// Skip identifier validation for trusted table/column expressions
+    pub fn trust_identifiers(mut self) -> Self {
+        self.builder.trust_identifiers();
+        self
+    }
+
+    /// Fail at build time if a plain selected column is missing from GROUP BY
+    pub fn strict_group_by(mut self) -> Self {
+        self.builder.strict_group_by();
+        self
+    }
+
     /// Add a WHERE clause (deprecated - use where_eq for safety)
     #[deprecated(note = "Use where_eq() with parameters for SQL injection protection")]
     pub fn where_clause(mut self, column: &str, value: crate::query::QueryValue) -> Self {
@@ -31,18 +74,105 @@ impl<'a, T: Model + FromRow> ModelQuery<'a, T> {
         self
     }
 
+    /// Add a WHERE condition with `:name` placeholders bound via `bind_named`
+    pub fn where_raw(mut self, condition: &str) -> Self {
+        self.builder.where_raw(condition);
+        self
+    }
+
+    /// Bind a value to a `:name` placeholder
+    pub fn bind_named(mut self, name: &str, value: crate::query::QueryValue) -> Self {
+        self.builder.bind_named(name, value);
+        self
+    }
+
     /// Add a WHERE clause with parameter (safe from SQL injection)
     pub fn where_eq(mut self, column: &str, value: crate::query::QueryValue) -> Self {
         self.builder.where_eq(column, value);
         self
     }
 
+    /// Add a `column IN (...)` WHERE clause
+    pub fn where_in(mut self, column: &str, values: &[crate::query::QueryValue]) -> Self {
+        self.builder.where_in(column, values);
+        self
+    }
+
+    /// OR a `column = value` condition as a new top-level term
+    ///
+    /// AND binds tighter than OR, so `where_eq(a).where_eq(b).or_where_eq(c)` means
+    /// `(a AND b) OR c`. Use `where_group` to parenthesize anything else.
+    pub fn or_where_eq(mut self, column: &str, value: crate::query::QueryValue) -> Self {
+        self.builder.or_where_eq(column, value);
+        self
+    }
+
+    /// OR a `column IN (...)` condition as a new top-level term (see `or_where_eq`)
+    pub fn or_where_in(mut self, column: &str, values: &[crate::query::QueryValue]) -> Self {
+        self.builder.or_where_in(column, values);
+        self
+    }
+
+    /// AND a parenthesized group of the conditions added by `f`
+    pub fn where_group<F: FnOnce(&mut QueryBuilderEnum)>(mut self, f: F) -> Self {
+        self.builder.where_group(f);
+        self
+    }
+
+    /// OR a parenthesized group of the conditions added by `f`
+    pub fn or_where_group<F: FnOnce(&mut QueryBuilderEnum)>(mut self, f: F) -> Self {
+        self.builder.or_where_group(f);
+        self
+    }
+
+    /// Add a `column <op> value` WHERE clause with a whitelisted comparison operator
+    pub fn where_cmp(mut self, column: &str, op: &str, value: crate::query::QueryValue) -> Self {
+        self.builder.where_cmp(column, op, value);
+        self
+    }
+
+    /// Add a case-insensitive equality WHERE clause (bypasses plain indexes on the column)
+    pub fn where_eq_ci(mut self, column: &str, value: crate::query::QueryValue) -> Self {
+        self.builder.where_eq_ci(column, value);
+        self
+    }
+
+    /// Add an inclusive `column BETWEEN low AND high` WHERE clause
+    pub fn where_between(mut self, column: &str, low: crate::query::QueryValue, high: crate::query::QueryValue) -> Self {
+        self.builder.where_between(column, low, high);
+        self
+    }
+
+    /// Add a `column NOT BETWEEN low AND high` WHERE clause
+    pub fn where_not_between(mut self, column: &str, low: crate::query::QueryValue, high: crate::query::QueryValue) -> Self {
+        self.builder.where_not_between(column, low, high);
+        self
+    }
+
+    /// Add a WHERE clause on a value inside a JSON column, e.g. path `profile.role`
+    pub fn where_json_eq(mut self, column: &str, path: &str, value: crate::query::QueryValue) -> Self {
+        self.builder.where_json_eq(column, path, value);
+        self
+    }
+
+    /// Add a full-text search WHERE clause (FTS5 on SQLite, FULLTEXT on MySQL)
+    pub fn where_match(mut self, columns: &[&str], query: crate::query::QueryValue) -> Self {
+        self.builder.where_match(columns, query);
+        self
+    }
+
     /// Add an ORDER BY clause
     pub fn order_by(mut self, column: &str, direction: OrderDirection) -> Self {
         self.builder.order_by(column, direction);
         self
     }
 
+    /// Add an ORDER BY on a trusted expression such as `LENGTH(name)`
+    pub fn order_by_raw(mut self, expression: &str, direction: OrderDirection) -> Self {
+        self.builder.order_by_raw(expression, direction);
+        self
+    }
+
     /// Add a LIMIT clause
     pub fn limit(mut self, limit: u64) -> Self {
         self.builder.limit(limit);
@@ -55,12 +185,24 @@ impl<'a, T: Model + FromRow> ModelQuery<'a, T> {
         self
     }
 
+    /// Fetch a 1-based page of `size` rows
+    pub fn page(mut self, number: u64, size: u64) -> Self {
+        self.builder.page(number, size);
+        self
+    }
+
     /// Add a JOIN clause
     pub fn join(mut self, table: &str, on: &str, join_type: JoinType) -> Self {
         self.builder.join(table, on, join_type);
         self
     }
 
+    /// Add a JOIN clause with parameters in the ON condition
+    pub fn join_on_params(mut self, table: &str, on: &str, params: &[crate::query::QueryValue], join_type: JoinType) -> Self {
+        self.builder.join_on_params(table, on, params, join_type);
+        self
+    }
+
     /// Add an INNER JOIN clause
     pub fn inner_join(mut self, table: &str, on: &str) -> Self {
         self.builder.inner_join(table, on);
@@ -79,28 +221,85 @@ impl<'a, T: Model + FromRow> ModelQuery<'a, T> {
         self
     }
 
-    /// Add a HAVING clause
+    /// Set the HAVING clause, replacing any HAVING conditions added so far
     pub fn having(mut self, condition: &str) -> Self {
         self.builder.having(condition);
         self
     }
 
+    /// AND a condition onto the HAVING clause
+    pub fn and_having(mut self, condition: &str) -> Self {
+        self.builder.and_having(condition);
+        self
+    }
+
+    /// Add a HAVING clause with parameters (safe from SQL injection)
+    pub fn having_params(mut self, condition: &str, params: &[crate::query::QueryValue]) -> Self {
+        self.builder.having_params(condition, params);
+        self
+    }
+
+    /// Select the number of `relation_table` rows whose `foreign_key` points at each
+    /// record, as a correlated subquery column named `alias`
+    ///
+    /// Read the counts with `get_with_counts`.
+    pub fn with_count(mut self, relation_table: &str, foreign_key: &str, alias: &str) -> Self {
+        let parent = self.builder.table().unwrap_or(T::table_name()).to_string();
+        let local_key = format!("{}.{}", parent, T::primary_key());
+        self.builder.select_related_count(alias, relation_table, foreign_key, &local_key);
+        self.count_aliases.push(alias.to_string());
+        self
+    }
+
+    /// Apply `f` to the query only if `condition` holds, keeping optional filters in
+    /// the chain
+    pub fn when<F>(self, condition: bool, f: F) -> Self
+    where
+        F: FnOnce(Self) -> Self,
+    {
+        if condition { f(self) } else { self }
+    }
+
     /// Add DISTINCT
     pub fn distinct(mut self) -> Self {
         self.builder.distinct();
         self
     }
 
+    /// Get the selected columns with their declared types
+    pub fn columns(&self) -> &[Column] {
+        self.builder.columns()
+    }
+
     /// Build and return the SQL query
     pub fn to_sql(&self) -> Result<String> {
         self.builder.build()
     }
 
+    /// Build the SQL query and return it with its parameters, in binding order
+    pub fn to_sql_with_params(&self) -> Result<(String, Vec<crate::query::QueryValue>)> {
+        self.builder.build_with_params()
+    }
+
+    /// Print the SQL built so far and its parameters to stderr, then continue the chain
+    pub fn debug(self) -> Self {
+        match self.builder.build_with_params() {
+            Ok((sql, params)) => eprintln!("[orm] {} -- params: {:?}", sql, params),
+            Err(e) => eprintln!("[orm] /* {} */", e),
+        }
+        self
+    }
+
+    /// Return the database's query plan for this query
+    pub async fn explain(self) -> Result<Vec<serde_json::Value>> {
+        let (sql, params) = self.builder.build_with_params()?;
+        self.backend.explain(&sql, &params).await
+    }
+
     /// Execute the query and return all results
     pub async fn get(self) -> Result<Vec<T>> {
-        let sql = self.builder.build()?;
-        let params = self.builder.params();
-        let json_rows = self.backend.fetch_all_params(&sql, params).await?;
+        let (sql, params) = self.builder.build_with_params()?;
+        let json_rows = self.backend.fetch_all_params(&sql, &params).await?;
         
         json_rows
             .iter()
@@ -108,28 +307,213 @@ impl<'a, T: Model + FromRow> ModelQuery<'a, T> {
             .collect()
     }
 
+    /// Execute the query and return each record with the counts added by `with_count`,
+    /// keyed by alias
+    pub async fn get_with_counts(self) -> Result<Vec<(T, HashMap<String, i64>)>> {
+        let (sql, params) = self.builder.build_with_params()?;
+        let json_rows = self.backend.fetch_all_params(&sql, &params).await?;
+
+        json_rows
+            .iter()
+            .map(|json| {
+                let counts = self.count_aliases
+                    .iter()
+                    .map(|alias| {
+                        let count = json.get(alias).and_then(|v| v.as_i64()).ok_or_else(|| {
+                            Error::QueryError(format!("Failed to parse count {}", alias))
+                        })?;
+                        Ok((alias.clone(), count))
+                    })
+                    .collect::<Result<HashMap<_, _>>>()?;
+                Ok((T::from_json(json)?, counts))
+            })
+            .collect()
+    }
+
+    /// Fetch a 1-based page of `per_page` rows along with the total number of matching
+    /// rows. Add an `order_by` so pages are stable.
+    pub async fn paginate(self, page: u64, per_page: u64) -> Result<Page<T>> {
+        if per_page == 0 {
+            return Err(Error::QueryError("Page size must be greater than zero".to_string()));
+        }
+
+        let (sql, params) = self.builder.to_count_sql()?;
+        let total = match self.backend.fetch_one_params(&sql, &params).await? {
+            Some(row) => row.get("count")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| Error::QueryError("Failed to parse count result".to_string()))?,
+            None => 0,
+        };
+        let page = page.max(1);
+        let items = self.page(page, per_page).get().await?;
+
+        Ok(Page { items, total: total as u64, page, per_page })
+    }
+
+    /// Run the query in batches of `size` rows, calling `f` with each batch until
+    /// a short batch signals the end. Add an `order_by` so batches don't overlap.
+    pub async fn chunk<F>(self, size: u64, mut f: F) -> Result<()>
+    where
+        F: FnMut(Vec<T>) -> Result<()>,
+    {
+        if size == 0 {
+            return Err(Error::QueryError("Chunk size must be greater than zero".to_string()));
+        }
+
+        let mut offset = 0;
+        loop {
+            let batch = self.clone_query().limit(size).offset(offset).get().await?;
+            let len = batch.len() as u64;
+            if len > 0 {
+                f(batch)?;
+            }
+            if len < size {
+                return Ok(());
+            }
+            offset += size;
+        }
+    }
+
     /// Execute the query and return first result
     pub async fn first(self) -> Result<Option<T>> {
-        let sql = self.builder.build()?;
-        let params = self.builder.params();
-        let json_row = self.backend.fetch_one_params(&sql, params).await?;
+        let (sql, params) = self.builder.build_with_params()?;
+        let json_row = self.backend.fetch_one_params(&sql, &params).await?;
         
         match json_row {
             Some(json) => Ok(Some(T::from_json(&json)?)),
             None => Ok(None),
         }
     }
+
+    /// Count the rows matching this query
+    pub async fn count(self) -> Result<i64> {
+        self.count_expr("*", false).await
+    }
+
+    /// Count distinct values of a column among the rows matching this query
+    pub async fn count_distinct(self, column: &str) -> Result<i64> {
+        self.count_expr(column, true).await
+    }
+
+    async fn count_expr(mut self, column: &str, distinct: bool) -> Result<i64> {
+        self.builder
+            .select(&[])
+            .select_aggregate("count", "COUNT", column, distinct);
+        let (sql, params) = self.builder.build_with_params()?;
+        let row = self.backend.fetch_one_params(&sql, &params).await?;
+
+        match row {
+            Some(json) => json.get("count")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| Error::QueryError("Failed to parse count result".to_string())),
+            None => Ok(0),
+        }
+    }
+
+    /// Whether any row matches this query, fetching at most one
+    pub async fn exists(mut self) -> Result<bool> {
+        self.builder
+            .select(&[Column::new("1", ColumnType::Integer)])
+            .limit(1);
+        let (sql, params) = self.builder.build_with_params()?;
+        Ok(self.backend.fetch_one_params(&sql, &params).await?.is_some())
+    }
+
+    /// Execute the query and return the first result, or `default` if there is none
+    pub async fn first_or(self, default: T) -> Result<T> {
+        Ok(self.first().await?.unwrap_or(default))
+    }
+
+    /// Execute the query and return the first result, or compute one if there is none
+    pub async fn first_or_else<F>(self, f: F) -> Result<T>
+    where
+        F: FnOnce() -> T,
+    {
+        Ok(self.first().await?.unwrap_or_else(f))
+    }
+}
+
+/// One page of query results, from `ModelQuery::paginate`
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Rows matching the query across all pages
+    pub total: u64,
+    /// The 1-based page number
+    pub page: u64,
+    pub per_page: u64,
+}
+
+impl<T> Page<T> {
+    /// Number of pages needed for all matching rows, or 0 when `per_page` is 0
+    pub fn total_pages(&self) -> u64 {
+        if self.per_page == 0 {
+            return 0;
+        }
+        self.total.div_ceil(self.per_page)
+    }
+
+    /// Whether there is a page after this one
+    pub fn has_next(&self) -> bool {
+        self.page < self.total_pages()
+    }
+}
+
+/// Run a model's `validator`, if it has one, as an `Error::Validation`
+fn check_valid<T: ModelCrud>(model: &T) -> Result<()> {
+    match model.validator() {
+        Some(validator) => validator.validate().map_err(Error::Validation),
+        None => Ok(()),
+    }
 }
 
 /// CRUD operations for models
 #[async_trait]
 pub trait ModelCrud: Model + FromRow {
+    /// Called by `create` with the column values about to be inserted; rewrite them
+    /// (e.g. hash a password) or return `Err` to abort the insert
+    fn before_create(&self, _values: &mut HashMap<String, Value>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called by `create` on the stored record after inserting
+    fn after_create(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// The validation `create`, `update` and `upsert` run before writing
+    ///
+    /// **Implementing `Validate` is not enough on its own.** Rust can't detect the
+    /// impl from here, so a model must also override this to return `Some(self)`;
+    /// without that override its `validate` is never called.
+    fn validator(&self) -> Option<&dyn Validate> {
+        None
+    }
+
+    /// Called by `update` before writing; an `Err` aborts the update
+    fn before_update(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called by `delete` before deleting; an `Err` aborts the delete
+    fn before_delete(&self) -> Result<()> {
+        Ok(())
+    }
+
     /// Start a query builder for this model
     fn query(backend: &dyn Backend) -> ModelQuery<'_, Self> {
         let mut query = ModelQuery::new(backend);
+        let types = Self::column_types();
         let columns: Vec<Column> = Self::all_columns()
             .iter()
-            .map(|name| Column::new(*name, ColumnType::Text))
+            .map(|name| {
+                let column_type = types
+                    .iter()
+                    .find(|(col, _)| col == name)
+                    .map(|(_, ty)| ty.clone())
+                    .unwrap_or(ColumnType::Text);
+                Column::new(*name, column_type)
+            })
             .collect();
         query.builder.select(&columns);
         query.builder.from(Self::table_name());
@@ -143,11 +527,46 @@ pub trait ModelCrud: Model + FromRow {
         query.first().await
     }
 
+    /// Find the records whose primary key is in `ids` with a single `IN` query
+    ///
+    /// Results follow the order of `ids`; missing ids are skipped.
+    async fn find_many(backend: &dyn Backend, ids: &[Value]) -> Result<Vec<Self>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let params: Vec<crate::query::QueryValue> = ids.iter().map(Value::to_query_value).collect();
+        let mut records = Self::query(backend).where_in(Self::primary_key(), &params).get().await?;
+
+        // Rows come back as I64 whatever integer type the caller passed
+        let same_key = |a: &Value, b: &Value| match (a.as_i64(), b.as_i64()) {
+            (Some(a), Some(b)) => a == b,
+            _ => a == b,
+        };
+        records.sort_by_key(|record| {
+            record
+                .primary_key_value()
+                .and_then(|pk| ids.iter().position(|id| same_key(id, &pk)))
+                .unwrap_or(ids.len())
+        });
+        Ok(records)
+    }
+
     /// Find all records
     async fn all(backend: &dyn Backend) -> Result<Vec<Self>> {
         Self::query(backend).get().await
     }
 
+    /// Fetch a 1-based page of records ordered by primary key, with the total count
+    ///
+    /// Prefer this over `all` for tables that can grow large.
+    async fn all_paginated(backend: &dyn Backend, page: u64, per_page: u64) -> Result<Page<Self>> {
+        Self::query(backend)
+            .order_by(Self::primary_key(), OrderDirection::Asc)
+            .paginate(page, per_page)
+            .await
+    }
+
     /// Find records matching a condition (deprecated - use parameterized queries)
     #[deprecated(note = "Use query().where_eq() with parameters for SQL injection protection")]
     async fn where_clause(backend: &dyn Backend, column: &str, value: crate::query::QueryValue) -> Result<Vec<Self>> {
@@ -184,19 +603,29 @@ pub trait ModelCrud: Model + FromRow {
 
     /// Create a new record
     async fn create(backend: &dyn Backend, values: &Self) -> Result<Self> {
+        let mut data = values.to_values();
+        values.before_create(&mut data)?;
+        check_valid(values)?;
+
         let mut builder = backend.query_builder();
-        let data = values.to_values();
+        let table = values.table_name_dyn();
         
-        let columns: Vec<&str> = data.keys().map(|s| s.as_str()).collect();
-        let query_values: Vec<crate::query::QueryValue> = data.values().map(|v| v.to_query_value()).collect();
+        // `to_values` already copied each string out of the model; move those copies into
+        // the params rather than cloning them a second time
+        let (columns, query_values): (Vec<String>, Vec<crate::query::QueryValue>) = data
+            .into_iter()
+            .map(|(column, value)| (column, value.into_query_value()))
+            .unzip();
+        let columns: Vec<&str> = columns.iter().map(String::as_str).collect();
 
         // Try using RETURNING if supported (SQLite)
-        if backend.supports_feature(crate::backend::BackendFeature::Returning) {
-            let all_cols: Vec<&str> = Self::all_columns();
+        let mut created = if backend.supports_feature(crate::backend::BackendFeature::Returning) {
+            // Return every column so DB-defaulted extras don't break the statement;
+            // from_row only reads the fields the model knows
             let sql = builder
-                .insert_into(Self::table_name(), &columns)
-                .values_params(&query_values)
-                .returning(&all_cols)
+                .insert_into(&table, &columns)
+                .values_owned(query_values)
+                .returning(&["*"])
                 .build()?;
             
             let params = builder.params();
@@ -206,36 +635,32 @@ pub trait ModelCrud: Model + FromRow {
                 None => Err(Error::QueryError("Failed to create record".to_string())),
             }
         } else {
-            // For MySQL: execute insert, then fetch using LAST_INSERT_ID()
+            // For MySQL: execute insert, then fetch the row by its key
             let sql = builder
-                .insert_into(Self::table_name(), &columns)
-                .values_params(&query_values)
+                .insert_into(&table, &columns)
+                .values_owned(query_values)
                 .build()?;
 
             let params = builder.params();
-            backend.execute(&sql, params).await?;
-
-            // If the model has a primary key value, fetch it back
-            if let Some(pk_value) = values.primary_key_value() {
-                Self::find(backend, pk_value).await?
-                    .ok_or_else(|| Error::QueryError("Failed to fetch created record".to_string()))
-            } else {
-                // For auto-increment IDs, use LAST_INSERT_ID()
-                let last_id_sql = "SELECT LAST_INSERT_ID() as id";
-                #[allow(deprecated)]
-                let result = backend.fetch_one(last_id_sql).await?;
-                match result {
-                    Some(json) => {
-                        let id = json.get("id")
-                            .and_then(|v| v.as_i64())
-                            .ok_or_else(|| Error::QueryError("Failed to get last insert ID".to_string()))?;
-                        Self::find(backend, Value::I64(id)).await?
-                            .ok_or_else(|| Error::QueryError("Failed to fetch created record".to_string()))
-                    }
-                    None => Err(Error::QueryError("Failed to get last insert ID".to_string()))
-                }
-            }
-        }
+            let generated_id = backend.execute_returning(&sql, params).await?;
+
+            // Prefer the model's own key; fall back to the auto-increment id
+            let pk_value = match values.primary_key_value() {
+                Some(pk_value) => pk_value.to_query_value(),
+                None => crate::query::QueryValue::I64(generated_id.ok_or_else(|| {
+                    Error::QueryError("Failed to get last insert ID".to_string())
+                })?),
+            };
+            Self::query(backend)
+                .table(&table)
+                .where_eq(Self::primary_key(), pk_value)
+                .first()
+                .await?
+                .ok_or_else(|| Error::QueryError("Failed to fetch created record".to_string()))
+        }?;
+
+        created.after_create()?;
+        Ok(created)
     }
 
     /// Update a record
@@ -243,15 +668,17 @@ pub trait ModelCrud: Model + FromRow {
         let pk_value = self.primary_key_value().ok_or_else(|| {
             Error::QueryError("Cannot update record without primary key".to_string())
         })?;
+        self.before_update()?;
+        check_valid(self)?;
 
         let mut builder = backend.query_builder();
         let data = self.to_values();
 
-        builder.update(Self::table_name());
+        builder.update(&self.table_name_dyn());
         
-        for (col, val) in data.iter() {
+        for (col, val) in data {
             if col != Self::primary_key() {
-                builder.set_param(col, val.to_query_value());
+                builder.set_param(&col, val.into_query_value());
             }
         }
 
@@ -263,14 +690,157 @@ pub trait ModelCrud: Model + FromRow {
         Ok(())
     }
 
+    /// Apply the same SET values to every record whose primary key is in `ids`,
+    /// returning the number updated
+    async fn update_by_ids(backend: &dyn Backend, ids: &[Value], sets: &[(&str, crate::query::QueryValue)]) -> Result<u64> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+
+        let mut builder = backend.query_builder();
+        builder.update(Self::table_name());
+        for (column, value) in sets {
+            builder.set_param(column, value.clone());
+        }
+
+        let ids: Vec<crate::query::QueryValue> = ids.iter().map(Value::to_query_value).collect();
+        builder.where_in(Self::primary_key(), &ids);
+        let (sql, params) = builder.build_with_params()?;
+
+        backend.execute(&sql, &params).await
+    }
+
+    /// Insert the record if it has no primary key yet, otherwise update it
+    ///
+    /// After an insert, `self` is replaced with the stored row so the new id is populated.
+    async fn save(&mut self, backend: &dyn Backend) -> Result<()> {
+        if self.primary_key_value().is_some() {
+            self.update(backend).await
+        } else {
+            *self = Self::create(backend, self).await?;
+            Ok(())
+        }
+    }
+
+    /// Reload `self` from the database by primary key, discarding unsaved changes
+    ///
+    /// Fails with `Error::QueryError` if the row no longer exists.
+    async fn refresh(&mut self, backend: &dyn Backend) -> Result<()> {
+        let pk_value = self.primary_key_value().ok_or_else(|| {
+            Error::QueryError("Cannot refresh record without primary key".to_string())
+        })?;
+
+        let table = self.table_name_dyn();
+        let mut query = Self::query(backend).table(&table);
+        query.builder.where_eq(Self::primary_key(), pk_value.to_query_value());
+        query.builder.limit(1);
+
+        *self = query.first().await?.ok_or_else(|| {
+            Error::QueryError(format!(
+                "Cannot refresh {}: no row with {} = {}",
+                table,
+                Self::primary_key(),
+                pk_value.to_sql_string()
+            ))
+        })?;
+        Ok(())
+    }
+
+    /// Insert the record, or update its other columns if it conflicts on `conflict_columns`
+    /// (the primary key when empty), returning the stored row
+    ///
+    /// The conflict columns must have a unique index, and the record must carry a value for each.
+    /// Runs the same hooks as `create` whether the row ends up inserted or updated.
+    async fn upsert(backend: &dyn Backend, values: &Self, conflict_columns: &[&str]) -> Result<Self> {
+        let mut data = values.to_values();
+        values.before_create(&mut data)?;
+        check_valid(values)?;
+
+        let conflict_columns = if conflict_columns.is_empty() {
+            vec![Self::primary_key()]
+        } else {
+            conflict_columns.to_vec()
+        };
+        let table = values.table_name_dyn();
+
+        // The conflict values identify the row to read back afterwards
+        let key = conflict_columns
+            .iter()
+            .map(|column| {
+                data.get(*column)
+                    .map(|value| (*column, value.to_query_value()))
+                    .ok_or_else(|| Error::QueryError(format!("Cannot upsert without a value for {}", column)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let update_columns: Vec<String> = data
+            .keys()
+            .filter(|column| !conflict_columns.contains(&column.as_str()))
+            .cloned()
+            .collect();
+        let update_columns: Vec<&str> = update_columns.iter().map(String::as_str).collect();
+
+        let (columns, query_values): (Vec<String>, Vec<crate::query::QueryValue>) = data
+            .into_iter()
+            .map(|(column, value)| (column, value.into_query_value()))
+            .unzip();
+        let columns: Vec<&str> = columns.iter().map(String::as_str).collect();
+
+        let mut builder = backend.query_builder();
+        let sql = builder
+            .insert_into(&table, &columns)
+            .values_owned(query_values)
+            .on_conflict_update(&conflict_columns, &update_columns)
+            .build()?;
+        backend.execute(&sql, builder.params()).await?;
+
+        let mut query = Self::query(backend).table(&table);
+        for (column, value) in key {
+            query = query.where_eq(column, value);
+        }
+        let mut stored = query
+            .first()
+            .await?
+            .ok_or_else(|| Error::QueryError("Failed to fetch upserted record".to_string()))?;
+
+        stored.after_create()?;
+        Ok(stored)
+    }
+
+    /// Set `updated_at` to the current UTC time without changing other columns
+    ///
+    /// Only for models whose `timestamps()` is true. The time is bound in the
+    /// `YYYY-MM-DD HH:MM:SS` format `CURRENT_TIMESTAMP` produces.
+    async fn touch(&self, backend: &dyn Backend) -> Result<()> {
+        if !Self::timestamps() {
+            return Err(Error::QueryError(format!(
+                "Cannot touch {}: the model does not declare timestamps",
+                Self::table_name()
+            )));
+        }
+        let pk_value = self.primary_key_value().ok_or_else(|| {
+            Error::QueryError("Cannot touch record without primary key".to_string())
+        })?;
+
+        let mut builder = backend.query_builder();
+        builder.update(&self.table_name_dyn());
+        let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        builder.set_param("updated_at", crate::query::QueryValue::String(now));
+        builder.where_eq(Self::primary_key(), pk_value.to_query_value());
+        let (sql, params) = builder.build_with_params()?;
+
+        backend.execute(&sql, &params).await?;
+        Ok(())
+    }
+
     /// Delete a record
     async fn delete(&self, backend: &dyn Backend) -> Result<()> {
         let pk_value = self.primary_key_value().ok_or_else(|| {
             Error::QueryError("Cannot delete record without primary key".to_string())
         })?;
+        self.before_delete()?;
 
         let mut builder = backend.query_builder();
-        builder.delete_from(Self::table_name());
+        builder.delete_from(&self.table_name_dyn());
         builder.where_eq(Self::primary_key(), pk_value.to_query_value());
         let sql = builder.build()?;
         let params = builder.params();
@@ -279,41 +849,39 @@ pub trait ModelCrud: Model + FromRow {
         Ok(())
     }
 
-    /// Delete records by condition (deprecated - use parameterized queries)
-    #[deprecated(note = "Use delete() on individual models or build custom parameterized queries")]
-    async fn delete_where(backend: &dyn Backend, condition: &str) -> Result<u64> {
-        #[allow(deprecated)]
-        {
-            let mut builder = backend.query_builder();
-            let sql = builder
-                .delete_from(Self::table_name())
-                .where_clause(condition)
-                .build()?;
+    /// Delete records where a column equals a value, returning the number deleted
+    async fn delete_where(backend: &dyn Backend, column: &str, value: crate::query::QueryValue) -> Result<u64> {
+        Self::delete_where_cmp(backend, column, "=", value).await
+    }
 
-            backend.execute_raw(&sql).await
-        }
+    /// Delete records matching `column <op> value`, returning the number deleted
+    async fn delete_where_cmp(backend: &dyn Backend, column: &str, op: &str, value: crate::query::QueryValue) -> Result<u64> {
+        let mut builder = backend.query_builder();
+        builder
+            .delete_from(Self::table_name())
+            .where_cmp(column, op, value);
+        let (sql, params) = builder.build_with_params()?;
+
+        backend.execute(&sql, &params).await
     }
 
     /// Count all records
     async fn count(backend: &dyn Backend) -> Result<i64> {
-        let mut builder = backend.query_builder();
-        let count_col = Column::new("COUNT(*) as count", ColumnType::BigInteger);
-        
-        let sql = builder
-            .select(&[count_col])
-            .from(Self::table_name())
-            .build()?;
+        Self::query(backend).count().await
+    }
 
-        #[allow(deprecated)]
-        let result = backend.fetch_one(&sql).await?;
-        match result {
-            Some(json) => {
-                let count = json.get("count")
-                    .and_then(|v| v.as_i64())
-                    .ok_or_else(|| Error::QueryError("Failed to parse count result".to_string()))?;
-                Ok(count)
-            }
-            None => Ok(0),
-        }
+    /// Count records where a column equals a value
+    async fn count_where(backend: &dyn Backend, column: &str, value: crate::query::QueryValue) -> Result<i64> {
+        Self::query(backend).where_eq(column, value).count().await
+    }
+
+    /// Whether a record exists where a column equals a value
+    async fn exists_by(backend: &dyn Backend, column: &str, value: crate::query::QueryValue) -> Result<bool> {
+        Self::query(backend).where_eq(column, value).exists().await
+    }
+
+    /// Whether a record exists with this primary key
+    async fn exists_by_id(backend: &dyn Backend, id: Value) -> Result<bool> {
+        Self::exists_by(backend, Self::primary_key(), id.to_query_value()).await
     }
 }
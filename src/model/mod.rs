@@ -1,14 +1,16 @@
 pub mod traits;
 pub mod crud;
+pub mod validate;
 
-pub use traits::{Model, FromRow};
-pub use crud::{ModelCrud, ModelQuery};
+pub use traits::{Model, FromRow, FromValue, RowExt, ToValue};
+pub use crud::{ModelCrud, ModelQuery, Page};
+pub use validate::{Validate, ValidationError};
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Represents a value that can be stored in the database
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum Value {
     Null,
@@ -17,6 +19,8 @@ pub enum Value {
     I64(i64),
     F64(f64),
     String(String),
+    /// Structured JSON (object or array), bound as its serialized text
+    Json(serde_json::Value),
 }
 
 impl From<bool> for Value {
@@ -25,6 +29,20 @@ impl From<bool> for Value {
     }
 }
 
+/// Widened to `I32`; bind `QueryValue::I8` directly where the narrow type matters
+impl From<i8> for Value {
+    fn from(v: i8) -> Self {
+        Value::I32(v.into())
+    }
+}
+
+/// Widened to `I32`; bind `QueryValue::I16` directly where the narrow type matters
+impl From<i16> for Value {
+    fn from(v: i16) -> Self {
+        Value::I32(v.into())
+    }
+}
+
 impl From<i32> for Value {
     fn from(v: i32) -> Self {
         Value::I32(v)
@@ -55,6 +73,12 @@ impl From<&str> for Value {
     }
 }
 
+impl From<serde_json::Value> for Value {
+    fn from(v: serde_json::Value) -> Self {
+        Value::Json(v)
+    }
+}
+
 impl From<Option<String>> for Value {
     fn from(v: Option<String>) -> Self {
         v.map(Value::String).unwrap_or(Value::Null)
@@ -75,6 +99,7 @@ impl Value {
             Value::I64(n) => n.to_string(),
             Value::F64(n) => n.to_string(),
             Value::String(s) => format!("'{}'", s.replace('\'', "''")),
+            Value::Json(v) => format!("'{}'", v.to_string().replace('\'', "''")),
         }
     }
 
@@ -87,6 +112,58 @@ impl Value {
             Value::I64(n) => crate::query::QueryValue::I64(*n),
             Value::F64(n) => crate::query::QueryValue::F64(*n),
             Value::String(s) => crate::query::QueryValue::String(s.clone()),
+            Value::Json(v) => crate::query::QueryValue::String(v.to_string()),
+        }
+    }
+
+    /// Convert to QueryValue, moving a string instead of cloning it
+    pub fn into_query_value(self) -> crate::query::QueryValue {
+        self.into()
+    }
+
+    /// Get as i64, widening 32-bit integers
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::I64(n) => Some(*n),
+            Value::I32(n) => Some(*n as i64),
+            _ => None,
+        }
+    }
+
+    /// Get as i32, narrowing 64-bit integers that fit
+    pub fn as_i32(&self) -> Option<i32> {
+        match self {
+            Value::I32(n) => Some(*n),
+            Value::I64(n) => i32::try_from(*n).ok(),
+            _ => None,
+        }
+    }
+
+    /// Get as f64, converting integers
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::F64(n) => Some(*n),
+            Value::I32(n) => Some(*n as f64),
+            Value::I64(n) => Some(*n as f64),
+            _ => None,
+        }
+    }
+
+    /// Get as bool, treating integer 0/1 as false/true (SQLite stores booleans as integers)
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            Value::I32(0) | Value::I64(0) => Some(false),
+            Value::I32(1) | Value::I64(1) => Some(true),
+            _ => None,
+        }
+    }
+
+    /// Get as a string slice
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
         }
     }
 }
@@ -1,5 +1,6 @@
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::model::{Row, Value};
+use crate::schema::ColumnType;
 use std::collections::HashMap;
 
 /// Core trait that all models must implement
@@ -7,6 +8,11 @@ pub trait Model: Sized + Send + Sync {
     /// The name of the database table
     fn table_name() -> &'static str;
 
+    /// The table name for this instance, for routing rows to prefixed or sharded tables
+    fn table_name_dyn(&self) -> String {
+        Self::table_name().to_string()
+    }
+
     /// The primary key column name
     fn primary_key() -> &'static str {
         "id"
@@ -15,9 +21,24 @@ pub trait Model: Sized + Send + Sync {
     /// Get the primary key value for this instance
     fn primary_key_value(&self) -> Option<Value>;
 
+    /// Whether the table has `created_at`/`updated_at` columns (see `TableBuilder::timestamps`)
+    fn timestamps() -> bool {
+        false
+    }
+
     /// Convert model to a map of column names to values
+    ///
+    /// The map owns its values, so every string column is copied out of the model.
     fn to_values(&self) -> HashMap<String, Value>;
 
+    /// Build a model from a map of column names to values, the inverse of `to_values`
+    fn from_values(values: HashMap<String, Value>) -> Result<Self>
+    where
+        Self: FromRow,
+    {
+        Self::from_row(&values)
+    }
+
     /// Get the column names for this model (excluding primary key if auto-increment)
     fn columns() -> Vec<&'static str>;
 
@@ -27,6 +48,14 @@ pub trait Model: Sized + Send + Sync {
         cols.extend(Self::columns());
         cols
     }
+
+    /// Get the declared type of each column (defaults to Text for every column)
+    fn column_types() -> Vec<(&'static str, ColumnType)> {
+        Self::all_columns()
+            .into_iter()
+            .map(|name| (name, ColumnType::Text))
+            .collect()
+    }
 }
 
 /// Trait for converting database rows into model instances
@@ -37,7 +66,7 @@ pub trait FromRow: Sized {
     /// Convert a JSON value into a model instance
     fn from_json(value: &serde_json::Value) -> Result<Self> {
         let obj = value.as_object().ok_or_else(|| {
-            crate::error::Error::SerializationError("Expected JSON object".to_string())
+            Error::SerializationError("Expected JSON object".to_string())
         })?;
 
         let mut row = HashMap::new();
@@ -55,7 +84,7 @@ pub trait FromRow: Sized {
                     }
                 }
                 serde_json::Value::String(s) => Value::String(s.clone()),
-                _ => Value::Null,
+                serde_json::Value::Object(_) | serde_json::Value::Array(_) => Value::Json(val.clone()),
             };
             row.insert(key.clone(), value);
         }
@@ -63,3 +92,135 @@ pub trait FromRow: Sized {
         Self::from_row(&row)
     }
 }
+
+/// Conversion from a column `Value` into a field type, e.g. an enum stored as text
+pub trait FromValue: Sized {
+    fn from_value(value: &Value) -> Result<Self>;
+}
+
+/// Conversion from a field type into a column `Value`
+pub trait ToValue {
+    fn to_value(&self) -> Value;
+}
+
+macro_rules! impl_value_conversions {
+    ($ty:ty, $variant:ident, $accessor:ident, $expected:literal) => {
+        impl FromValue for $ty {
+            fn from_value(value: &Value) -> Result<Self> {
+                value.$accessor().map(Into::into).ok_or_else(|| unexpected_value($expected, value))
+            }
+        }
+
+        impl ToValue for $ty {
+            fn to_value(&self) -> Value {
+                Value::$variant(self.clone())
+            }
+        }
+    };
+}
+
+impl_value_conversions!(i32, I32, as_i32, "integer");
+impl_value_conversions!(i64, I64, as_i64, "integer");
+impl_value_conversions!(f64, F64, as_f64, "number");
+impl_value_conversions!(bool, Bool, as_bool, "boolean");
+impl_value_conversions!(String, String, as_str, "string");
+
+impl<T: FromValue> FromValue for Option<T> {
+    fn from_value(value: &Value) -> Result<Self> {
+        match value {
+            Value::Null => Ok(None),
+            value => T::from_value(value).map(Some),
+        }
+    }
+}
+
+impl<T: ToValue> ToValue for Option<T> {
+    fn to_value(&self) -> Value {
+        self.as_ref().map(ToValue::to_value).unwrap_or(Value::Null)
+    }
+}
+
+/// JSON columns come back from the driver as text, so strings are parsed
+impl FromValue for serde_json::Value {
+    fn from_value(value: &Value) -> Result<Self> {
+        match value {
+            Value::Null => Ok(serde_json::Value::Null),
+            Value::Json(v) => Ok(v.clone()),
+            Value::String(s) => serde_json::from_str(s)
+                .map_err(|e| Error::SerializationError(format!("Invalid JSON: {}", e))),
+            value => Err(unexpected_value("JSON", value)),
+        }
+    }
+}
+
+impl ToValue for serde_json::Value {
+    fn to_value(&self) -> Value {
+        Value::Json(self.clone())
+    }
+}
+
+fn unexpected_value(expected: &str, value: &Value) -> Error {
+    Error::SerializationError(format!("Expected {}, got {:?}", expected, value))
+}
+
+/// Typed accessors for reading columns out of a `Row`
+pub trait RowExt {
+    /// Get a required integer column
+    fn get_i64(&self, column: &str) -> Result<i64>;
+
+    /// Get a required 32-bit integer column
+    fn get_i32(&self, column: &str) -> Result<i32>;
+
+    /// Get a required string column
+    fn get_string(&self, column: &str) -> Result<String>;
+
+    /// Get a required boolean column (SQLite stores booleans as integers)
+    fn get_bool(&self, column: &str) -> Result<bool>;
+
+    /// Get a nullable integer column
+    fn get_opt_i64(&self, column: &str) -> Option<i64>;
+
+    /// Get a column as any `FromValue` type; a missing column reads as NULL
+    fn get_as<T: FromValue>(&self, column: &str) -> Result<T>;
+}
+
+impl RowExt for Row {
+    fn get_i64(&self, column: &str) -> Result<i64> {
+        get_typed(self, column, "i64", Value::as_i64)
+    }
+
+    fn get_i32(&self, column: &str) -> Result<i32> {
+        get_typed(self, column, "i32", Value::as_i32)
+    }
+
+    fn get_string(&self, column: &str) -> Result<String> {
+        get_typed(self, column, "string", |value| value.as_str().map(str::to_string))
+    }
+
+    fn get_bool(&self, column: &str) -> Result<bool> {
+        get_typed(self, column, "bool", Value::as_bool)
+    }
+
+    fn get_opt_i64(&self, column: &str) -> Option<i64> {
+        self.get(column).and_then(Value::as_i64)
+    }
+
+    fn get_as<T: FromValue>(&self, column: &str) -> Result<T> {
+        T::from_value(self.get(column).unwrap_or(&Value::Null)).map_err(|e| match e {
+            Error::SerializationError(msg) => Error::SerializationError(format!("{}: {}", column, msg)),
+            e => e,
+        })
+    }
+}
+
+/// Read a column with `convert`, telling a missing column apart from a value of the wrong type
+fn get_typed<T>(row: &Row, column: &str, expected: &str, convert: impl FnOnce(&Value) -> Option<T>) -> Result<T> {
+    let value = row.get(column).ok_or_else(|| missing_column(column))?;
+    convert(value).ok_or_else(|| {
+        Error::SerializationError(format!("{}: expected {}, got {:?}", column, expected, value))
+    })
+}
+
+fn missing_column(column: &str) -> Error {
+    Error::SerializationError(format!("Missing {}", column))
+}
@@ -0,0 +1,41 @@
+/// A field-level validation failure
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    pub field: String,
+    pub message: String,
+}
+
+impl ValidationError {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// Field validation run before a model is written to the database
+///
+/// `ModelCrud::create`, `update` and `upsert` only run it for models that also
+/// override `ModelCrud::validator` to return `Some(self)`. Implementing this trait
+/// alone does not enable validation:
+///
+/// ```ignore
+/// impl Validate for Contact { /* ... */ }
+///
+/// impl ModelCrud for Contact {
+///     fn validator(&self) -> Option<&dyn Validate> {
+///         Some(self)
+///     }
+/// }
+/// ```
+pub trait Validate {
+    /// Check every field, returning all failures rather than stopping at the first
+    fn validate(&self) -> Result<(), Vec<ValidationError>>;
+}
@@ -1,11 +1,65 @@
 use crate::error::Result;
-use crate::query::{JoinType, OrderDirection, QueryBuilder, QueryValue};
-use crate::schema::Column;
+use crate::query::{aggregate, window, JoinType, OrderDirection, QueryBuilder, QueryValue};
+use crate::schema::{Column, ColumnType};
+use crate::utils::{validate_identifier, validate_table_reference};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Dialect {
     SQLite,
     MySQL,
+    Postgres,
+}
+
+impl Dialect {
+    /// A fresh query builder for this dialect, for rendering SQL without a connection
+    pub fn query_builder(&self) -> QueryBuilderEnum {
+        QueryBuilderEnum::new(*self)
+    }
+
+    /// Parameter marker for the 1-based parameter `index` in a statement
+    pub fn placeholder(&self, index: usize) -> String {
+        match self {
+            Dialect::SQLite | Dialect::MySQL => "?".to_string(),
+            Dialect::Postgres => format!("${}", index),
+        }
+    }
+
+    /// Whether INSERT/UPDATE/DELETE accept a RETURNING clause
+    fn supports_returning(&self) -> bool {
+        matches!(self, Dialect::SQLite | Dialect::Postgres)
+    }
+
+    /// Whether FULL OUTER JOIN is available (SQLite since 3.39; never on MySQL)
+    fn supports_full_join(&self) -> bool {
+        matches!(self, Dialect::SQLite | Dialect::Postgres)
+    }
+
+    /// Rewrite each `?` marker (outside string literals) into this dialect's placeholder
+    fn number_placeholders(&self, sql: &str) -> String {
+        if *self != Dialect::Postgres {
+            return sql.to_string();
+        }
+
+        let mut result = String::with_capacity(sql.len());
+        let mut index = 0;
+        let mut in_string = false;
+
+        for ch in sql.chars() {
+            match ch {
+                '\'' => {
+                    in_string = !in_string;
+                    result.push(ch);
+                }
+                '?' if !in_string => {
+                    index += 1;
+                    result.push_str(&self.placeholder(index));
+                }
+                _ => result.push(ch),
+            }
+        }
+
+        result
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -16,14 +70,28 @@ enum QueryType {
     Delete,
 }
 
+/// Clause a parameter belongs to, in the order clauses appear in the statement
+#[derive(Debug, Clone, Copy)]
+enum ParamClause {
+    Join = 0,
+    Set = 1,
+    Where = 2,
+    Having = 3,
+}
+
 /// Unified query builder for all database backends
+#[derive(Debug, Clone)]
 pub struct QueryBuilderEnum {
     dialect: Dialect,
     query_type: QueryType,
-    columns: Vec<String>,
+    columns: Vec<Column>,
     table: Option<String>,
     where_clauses: Vec<String>,
+    or_where_indices: Vec<usize>, // where_clauses joined to the ones before them with OR
+    where_columns: Vec<String>,
+    where_operators: Vec<String>,
     order_by: Vec<(String, OrderDirection)>,
+    order_by_columns: Vec<String>,
     limit: Option<u64>,
     offset: Option<u64>,
     insert_table: Option<String>,
@@ -33,11 +101,20 @@ pub struct QueryBuilderEnum {
     update_sets: Vec<(String, String)>,
     delete_table: Option<String>,
     returning_columns: Vec<String>,
+    on_conflict: Option<(Vec<String>, Vec<String>)>, // (conflict columns, columns to update)
     joins: Vec<(JoinType, String, String)>, // (type, table, on_condition)
+    related_counts: Vec<[String; 4]>, // (alias, table, foreign key, local key)
     group_by_columns: Vec<String>,
-    having_clause: Option<String>,
+    having_clauses: Vec<String>,
     is_distinct: bool,
     params: Vec<QueryValue>,
+    param_names: Vec<Option<String>>,
+    param_counts: [usize; 4],
+    named_params: Vec<(String, QueryValue)>,
+    trusted_identifiers: bool,
+    safe_mode: bool,
+    strict_group_by: bool,
+    placeholder_mismatches: Vec<String>, // raw conditions whose `?` count differs from their params
 }
 
 impl QueryBuilderEnum {
@@ -48,7 +125,11 @@ impl QueryBuilderEnum {
             columns: Vec::new(),
             table: None,
             where_clauses: Vec::new(),
+            or_where_indices: Vec::new(),
+            where_columns: Vec::new(),
+            where_operators: Vec::new(),
             order_by: Vec::new(),
+            order_by_columns: Vec::new(),
             limit: None,
             offset: None,
             insert_table: None,
@@ -58,26 +139,283 @@ impl QueryBuilderEnum {
             update_sets: Vec::new(),
             delete_table: None,
             returning_columns: Vec::new(),
+            on_conflict: None,
             joins: Vec::new(),
+            related_counts: Vec::new(),
             group_by_columns: Vec::new(),
-            having_clause: None,
+            having_clauses: Vec::new(),
             is_distinct: false,
             params: Vec::new(),
+            param_names: Vec::new(),
+            param_counts: [0; 4],
+            named_params: Vec::new(),
+            trusted_identifiers: false,
+            safe_mode: true,
+            strict_group_by: false,
+            placeholder_mismatches: Vec::new(),
         }
     }
 
+    /// Get the selected columns, including their declared types
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    /// Get the table the query selects from, if set
+    pub fn table(&self) -> Option<&str> {
+        self.table.as_deref()
+    }
+
+    /// Skip identifier validation at build time, for trusted table/column expressions
+    pub fn trust_identifiers(&mut self) -> &mut Self {
+        self.trusted_identifiers = true;
+        self
+    }
+
+    /// Allow an UPDATE or DELETE without a WHERE clause to affect every row
+    pub fn allow_full_table(&mut self) -> &mut Self {
+        self.safe_mode = false;
+        self
+    }
+
+    /// Require every plain selected column to appear in GROUP BY, as
+    /// `ONLY_FULL_GROUP_BY` does, failing at build time instead of on the server
+    pub fn strict_group_by(&mut self) -> &mut Self {
+        self.strict_group_by = true;
+        self
+    }
+
     /// Get the parameters for this query
     pub fn params(&self) -> &[QueryValue] {
         &self.params
     }
 
-    /// Add a parameter and return its placeholder
-    fn add_param(&mut self, value: QueryValue) -> String {
-        self.params.push(value);
-        match self.dialect {
-            Dialect::SQLite => "?".to_string(),
-            Dialect::MySQL => "?".to_string(),
+    /// Add a parameter for a clause and return its placeholder marker
+    ///
+    /// Parameters are kept in statement order (JOIN, SET/VALUES, WHERE, HAVING)
+    /// regardless of the order the builder methods are called in. Markers are
+    /// numbered for the dialect once the whole statement is built.
+    fn add_param(&mut self, clause: ParamClause, value: QueryValue) -> String {
+        self.add_slot(clause, value, None)
+    }
+
+    fn add_slot(&mut self, clause: ParamClause, value: QueryValue, name: Option<String>) -> String {
+        let index = clause as usize;
+        let position: usize = self.param_counts[..=index].iter().sum();
+        self.params.insert(position, value);
+        self.param_names.insert(position, name);
+        self.param_counts[index] += 1;
+        "?".to_string()
+    }
+
+    /// Drop every HAVING condition along with the parameters bound to it
+    fn clear_having(&mut self) {
+        let index = ParamClause::Having as usize;
+        let start: usize = self.param_counts[..index].iter().sum();
+        let end = start + self.param_counts[index];
+        self.params.drain(start..end);
+        self.param_names.drain(start..end);
+        self.param_counts[index] = 0;
+        self.having_clauses.clear();
+    }
+
+    /// Add a parameter slot for a `:name` marker, filled now or by a later `bind_named`
+    fn add_named_param(&mut self, clause: ParamClause, name: String) -> String {
+        let value = self
+            .named_params
+            .iter()
+            .find(|(bound, _)| *bound == name)
+            .map(|(_, value)| value.clone())
+            .unwrap_or(QueryValue::Null);
+        self.add_slot(clause, value, Some(name))
+    }
+
+    /// Register the parameters of a raw condition, returning it with `:name` markers
+    /// rewritten to positional markers
+    ///
+    /// `?` markers take the next value from `params`; a count that doesn't match
+    /// is recorded and fails the build.
+    fn add_condition(&mut self, clause: ParamClause, condition: &str, params: &[QueryValue]) -> String {
+        let (condition, markers) = split_named_params(condition);
+        let positional = markers.iter().filter(|marker| marker.is_none()).count();
+        if positional != params.len() {
+            self.placeholder_mismatches.push(format!(
+                "Condition `{}` has {} `?` placeholder(s) but {} parameter(s)",
+                condition,
+                positional,
+                params.len()
+            ));
+        }
+        let mut params = params.iter();
+
+        for marker in markers {
+            match marker {
+                Some(name) => {
+                    self.add_named_param(clause, name);
+                }
+                None => {
+                    if let Some(param) = params.next() {
+                        self.add_param(clause, param.clone());
+                    }
+                }
+            }
+        }
+
+        for param in params {
+            self.add_param(clause, param.clone());
+        }
+
+        condition
+    }
+
+    /// Reject table and column names that could smuggle SQL into the statement
+    ///
+    /// Selected columns and raw conditions are expressions and are not checked.
+    fn validate_identifiers(&self) -> Result<()> {
+        if self.trusted_identifiers {
+            return Ok(());
+        }
+
+        let tables = [&self.table, &self.insert_table, &self.update_table, &self.delete_table];
+        for table in tables.into_iter().flatten() {
+            validate_table_reference(table)?;
+        }
+        for (_, table, _) in &self.joins {
+            validate_table_reference(table)?;
+        }
+        for count in &self.related_counts {
+            for identifier in count {
+                validate_identifier(identifier)?;
+            }
+        }
+
+        let columns = self.where_columns.iter()
+            .chain(&self.order_by_columns)
+            .chain(&self.group_by_columns)
+            .chain(&self.insert_columns)
+            .chain(self.update_sets.iter().map(|(col, _)| col))
+            .chain(&self.returning_columns)
+            .chain(self.on_conflict.iter().flat_map(|(target, update)| target.iter().chain(update)));
+        for column in columns {
+            validate_identifier(column)?;
+        }
+
+        Ok(())
+    }
+
+    fn check_operators(&self) -> Result<()> {
+        const OPERATORS: [&str; 7] = ["=", "<>", "!=", "<", "<=", ">", ">="];
+        match self.where_operators.iter().find(|op| !OPERATORS.contains(&op.as_str())) {
+            Some(op) => Err(crate::error::Error::QueryError(format!(
+                "Invalid comparison operator: {}",
+                op
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    fn check_named_params(&self) -> Result<()> {
+        for name in self.param_names.iter().flatten() {
+            if !self.named_params.iter().any(|(bound, _)| bound == name) {
+                return Err(crate::error::Error::QueryError(format!(
+                    "Unbound named parameter :{}",
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_placeholders(&self) -> Result<()> {
+        match self.placeholder_mismatches.first() {
+            Some(mismatch) => Err(crate::error::Error::QueryError(mismatch.clone())),
+            None => Ok(()),
+        }
+    }
+
+    fn build_joins(&self) -> Result<String> {
+        let mut sql = String::new();
+        for (join_type, table, on) in &self.joins {
+            if *join_type == JoinType::Full && !self.dialect.supports_full_join() {
+                return Err(crate::error::Error::QueryError(format!(
+                    "FULL OUTER JOIN {} is not supported by {:?}; emulate it as a LEFT JOIN \
+                     UNION a RIGHT JOIN that keeps only rows missing on the left",
+                    table, self.dialect
+                )));
+            }
+            let join_str = match join_type {
+                JoinType::Inner => "INNER JOIN",
+                JoinType::Left => "LEFT JOIN",
+                JoinType::Right => "RIGHT JOIN",
+                JoinType::Full => "FULL OUTER JOIN",
+            };
+            sql.push_str(&format!(" {} {} ON {}", join_str, table, on));
         }
+        Ok(sql)
+    }
+
+    /// Join the most recent WHERE condition to the ones before it with OR
+    fn mark_last_where_or(&mut self) {
+        if self.where_clauses.len() > 1 {
+            self.or_where_indices.push(self.where_clauses.len() - 1);
+        }
+    }
+
+    /// In safe mode, refuse an UPDATE/DELETE that would touch every row
+    fn check_full_table(&self) -> Result<()> {
+        let statement = match self.query_type {
+            QueryType::Update => "UPDATE",
+            QueryType::Delete => "DELETE",
+            _ => return Ok(()),
+        };
+        if self.safe_mode && self.where_clauses.is_empty() {
+            return Err(crate::error::Error::QueryError(format!(
+                "{} without a WHERE clause would affect every row; call allow_full_table() to permit it",
+                statement
+            )));
+        }
+        Ok(())
+    }
+
+    /// In strict GROUP BY mode, refuse a selected column that is neither grouped nor
+    /// aggregated
+    ///
+    /// Only plain (optionally table-qualified) column names are checked; aggregates,
+    /// window functions and other expressions are left to the database.
+    fn check_group_by(&self) -> Result<()> {
+        if !self.strict_group_by || self.group_by_columns.is_empty() {
+            return Ok(());
+        }
+        // `users.id` and `id` are treated as the same column unless both are qualified
+        let same_column = |a: &str, b: &str| {
+            a == b || (!(a.contains('.') && b.contains('.'))
+                && a.rsplit('.').next() == b.rsplit('.').next())
+        };
+        for column in &self.columns {
+            let name = column.name();
+            if validate_identifier(name).is_err() {
+                continue;
+            }
+            let grouped = self.group_by_columns.iter().any(|group| same_column(group, name));
+            if !grouped {
+                return Err(crate::error::Error::QueryError(format!(
+                    "Column {} must appear in GROUP BY or be used in an aggregate function",
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Joined UPDATE/DELETE use MySQL's multi-table syntax, which other dialects lack
+    fn check_mutation_joins(&self, statement: &str) -> Result<()> {
+        if !self.joins.is_empty() && self.dialect != Dialect::MySQL {
+            return Err(crate::error::Error::QueryError(format!(
+                "{} with JOIN is only supported for MySQL",
+                statement
+            )));
+        }
+        Ok(())
     }
 
     fn build_select(&self) -> Result<String> {
@@ -90,7 +428,8 @@ impl QueryBuilderEnum {
         if self.columns.is_empty() {
             sql.push('*');
         } else {
-            sql.push_str(&self.columns.join(", "));
+            let names: Vec<&str> = self.columns.iter().map(|c| c.name()).collect();
+            sql.push_str(&names.join(", "));
         }
 
         if let Some(table) = &self.table {
@@ -98,20 +437,11 @@ impl QueryBuilderEnum {
             sql.push_str(table);
         }
 
-        // Add JOINs
-        for (join_type, table, on) in &self.joins {
-            let join_str = match join_type {
-                JoinType::Inner => "INNER JOIN",
-                JoinType::Left => "LEFT JOIN",
-                JoinType::Right => "RIGHT JOIN",
-                JoinType::Full => "FULL OUTER JOIN",
-            };
-            sql.push_str(&format!(" {} {} ON {}", join_str, table, on));
-        }
+        sql.push_str(&self.build_joins()?);
 
         if !self.where_clauses.is_empty() {
             sql.push_str(" WHERE ");
-            sql.push_str(&self.where_clauses.join(" AND "));
+            sql.push_str(&join_conditions(&self.where_clauses, &self.or_where_indices));
         }
 
         // Add GROUP BY
@@ -121,9 +451,9 @@ impl QueryBuilderEnum {
         }
 
         // Add HAVING
-        if let Some(having) = &self.having_clause {
+        if !self.having_clauses.is_empty() {
             sql.push_str(" HAVING ");
-            sql.push_str(having);
+            sql.push_str(&self.having_clauses.join(" AND "));
         }
 
         if !self.order_by.is_empty() {
@@ -136,8 +466,15 @@ impl QueryBuilderEnum {
             sql.push_str(&order_clauses.join(", "));
         }
 
-        if let Some(limit) = self.limit {
-            sql.push_str(&format!(" LIMIT {}", limit));
+        match (self.limit, self.offset) {
+            (Some(limit), _) => sql.push_str(&format!(" LIMIT {}", limit)),
+            // MySQL and SQLite only accept OFFSET after a LIMIT, so use an unbounded one
+            (None, Some(_)) => match self.dialect {
+                Dialect::MySQL => sql.push_str(" LIMIT 18446744073709551615"),
+                Dialect::SQLite => sql.push_str(" LIMIT -1"),
+                Dialect::Postgres => {}
+            },
+            (None, None) => {}
         }
 
         if let Some(offset) = self.offset {
@@ -147,6 +484,37 @@ impl QueryBuilderEnum {
         Ok(sql)
     }
 
+    /// Upsert clause: `ON CONFLICT ... DO UPDATE` on SQLite/Postgres, `ON DUPLICATE KEY UPDATE` on MySQL
+    fn build_on_conflict(&self, target: &[String], update: &[String]) -> Result<String> {
+        if self.dialect == Dialect::MySQL {
+            // MySQL resolves the conflict from the table's unique keys, so the target is implicit;
+            // assigning a column to itself turns the conflict into a no-op
+            let sets: Vec<String> = if update.is_empty() {
+                let column = target.first().unwrap_or(&self.insert_columns[0]);
+                vec![format!("{} = {}", column, column)]
+            } else {
+                update.iter().map(|c| format!("{} = VALUES({})", c, c)).collect()
+            };
+            return Ok(format!(" ON DUPLICATE KEY UPDATE {}", sets.join(", ")));
+        }
+
+        if target.is_empty() {
+            if update.is_empty() {
+                return Ok(" ON CONFLICT DO NOTHING".to_string());
+            }
+            return Err(crate::error::Error::QueryError(
+                "ON CONFLICT DO UPDATE needs at least one conflict column".to_string(),
+            ));
+        }
+
+        let target = target.join(", ");
+        if update.is_empty() {
+            return Ok(format!(" ON CONFLICT ({}) DO NOTHING", target));
+        }
+        let sets: Vec<String> = update.iter().map(|c| format!("{} = excluded.{}", c, c)).collect();
+        Ok(format!(" ON CONFLICT ({}) DO UPDATE SET {}", target, sets.join(", ")))
+    }
+
     fn build_insert(&self) -> Result<String> {
         let table = self.insert_table.as_ref().ok_or_else(|| {
             crate::error::Error::QueryError("No table specified for INSERT".to_string())
@@ -178,8 +546,12 @@ impl QueryBuilderEnum {
 
         sql.push_str(&value_groups.join(", "));
 
-        // RETURNING is SQLite-specific
-        if self.dialect == Dialect::SQLite && !self.returning_columns.is_empty() {
+        if let Some((target, update)) = &self.on_conflict {
+            sql.push_str(&self.build_on_conflict(target, update)?);
+        }
+
+        // RETURNING is not supported by MySQL
+        if self.dialect.supports_returning() && !self.returning_columns.is_empty() {
             sql.push_str(" RETURNING ");
             sql.push_str(&self.returning_columns.join(", "));
         }
@@ -198,7 +570,9 @@ impl QueryBuilderEnum {
             ));
         }
 
-        let mut sql = format!("UPDATE {} SET ", table);
+        self.check_mutation_joins("UPDATE")?;
+
+        let mut sql = format!("UPDATE {}{} SET ", table, self.build_joins()?);
 
         let set_clauses: Vec<String> = self
             .update_sets
@@ -210,11 +584,11 @@ impl QueryBuilderEnum {
 
         if !self.where_clauses.is_empty() {
             sql.push_str(" WHERE ");
-            sql.push_str(&self.where_clauses.join(" AND "));
+            sql.push_str(&join_conditions(&self.where_clauses, &self.or_where_indices));
         }
 
-        // RETURNING is SQLite-specific
-        if self.dialect == Dialect::SQLite && !self.returning_columns.is_empty() {
+        // RETURNING is not supported by MySQL
+        if self.dialect.supports_returning() && !self.returning_columns.is_empty() {
             sql.push_str(" RETURNING ");
             sql.push_str(&self.returning_columns.join(", "));
         }
@@ -227,15 +601,25 @@ impl QueryBuilderEnum {
             crate::error::Error::QueryError("No table specified for DELETE".to_string())
         })?;
 
-        let mut sql = format!("DELETE FROM {}", table);
+        self.check_mutation_joins("DELETE")?;
+
+        let mut sql = if self.joins.is_empty() {
+            format!("DELETE FROM {}", table)
+        } else {
+            // With an alias, MySQL requires the alias (not the table) as the delete target
+            match split_table_alias(table) {
+                (name, Some(alias)) => format!("DELETE {} FROM {} AS {}{}", alias, name, alias, self.build_joins()?),
+                (_, None) => format!("DELETE {} FROM {}{}", table, table, self.build_joins()?),
+            }
+        };
 
         if !self.where_clauses.is_empty() {
             sql.push_str(" WHERE ");
-            sql.push_str(&self.where_clauses.join(" AND "));
+            sql.push_str(&join_conditions(&self.where_clauses, &self.or_where_indices));
         }
 
-        // RETURNING is SQLite-specific
-        if self.dialect == Dialect::SQLite && !self.returning_columns.is_empty() {
+        // RETURNING is not supported by MySQL
+        if self.dialect.supports_returning() && !self.returning_columns.is_empty() {
             sql.push_str(" RETURNING ");
             sql.push_str(&self.returning_columns.join(", "));
         }
@@ -247,7 +631,7 @@ impl QueryBuilderEnum {
 impl QueryBuilder for QueryBuilderEnum {
     fn select(&mut self, columns: &[Column]) -> &mut Self {
         self.query_type = QueryType::Select;
-        self.columns = columns.iter().map(|c| c.name().to_string()).collect();
+        self.columns = columns.to_vec();
         self
     }
 
@@ -261,14 +645,173 @@ impl QueryBuilder for QueryBuilderEnum {
         self
     }
 
+    fn where_raw(&mut self, condition: &str) -> &mut Self {
+        let condition = self.add_condition(ParamClause::Where, condition, &[]);
+        self.where_clauses.push(condition);
+        self
+    }
+
+    fn bind_named(&mut self, name: &str, value: QueryValue) -> &mut Self {
+        let name = name.trim_start_matches(':');
+        for (slot, slot_name) in self.params.iter_mut().zip(&self.param_names) {
+            if slot_name.as_deref() == Some(name) {
+                *slot = value.clone();
+            }
+        }
+        match self.named_params.iter_mut().find(|(bound, _)| bound == name) {
+            Some((_, bound)) => *bound = value,
+            None => self.named_params.push((name.to_string(), value)),
+        }
+        self
+    }
+
     fn where_eq(&mut self, column: &str, value: QueryValue) -> &mut Self {
-        let placeholder = self.add_param(value);
+        self.where_columns.push(column.to_string());
+        let placeholder = self.add_param(ParamClause::Where, value);
         self.where_clauses.push(format!("{} = {}", column, placeholder));
         self
     }
 
+    fn or_where_eq(&mut self, column: &str, value: QueryValue) -> &mut Self {
+        self.where_eq(column, value);
+        self.mark_last_where_or();
+        self
+    }
+
+    fn or_where_in(&mut self, column: &str, values: &[QueryValue]) -> &mut Self {
+        self.where_in(column, values);
+        self.mark_last_where_or();
+        self
+    }
+
+    fn where_group<F: FnOnce(&mut Self)>(&mut self, f: F) -> &mut Self {
+        let start = self.where_clauses.len();
+        let or_start = self.or_where_indices.len();
+        f(self);
+
+        // Collapse the conditions `f` added into one parenthesized condition
+        let clauses = self.where_clauses.split_off(start);
+        let ors: Vec<usize> = self.or_where_indices.split_off(or_start).iter().map(|i| i - start).collect();
+        if !clauses.is_empty() {
+            self.where_clauses.push(format!("({})", join_conditions(&clauses, &ors)));
+        }
+        self
+    }
+
+    fn or_where_group<F: FnOnce(&mut Self)>(&mut self, f: F) -> &mut Self {
+        let start = self.where_clauses.len();
+        self.where_group(f);
+        if self.where_clauses.len() > start {
+            self.mark_last_where_or();
+        }
+        self
+    }
+
+    fn where_in(&mut self, column: &str, values: &[QueryValue]) -> &mut Self {
+        self.where_columns.push(column.to_string());
+        // `IN ()` is a syntax error; an empty list matches nothing
+        if values.is_empty() {
+            self.where_clauses.push("1 = 0".to_string());
+            return self;
+        }
+        let placeholders: Vec<String> = values
+            .iter()
+            .map(|v| self.add_param(ParamClause::Where, v.clone()))
+            .collect();
+        self.where_clauses.push(format!("{} IN ({})", column, placeholders.join(", ")));
+        self
+    }
+
+    fn where_cmp(&mut self, column: &str, op: &str, value: QueryValue) -> &mut Self {
+        self.where_columns.push(column.to_string());
+        self.where_operators.push(op.to_string());
+        let placeholder = self.add_param(ParamClause::Where, value);
+        self.where_clauses.push(format!("{} {} {}", column, op, placeholder));
+        self
+    }
+
+    fn where_eq_ci(&mut self, column: &str, value: QueryValue) -> &mut Self {
+        self.where_columns.push(column.to_string());
+        let placeholder = self.add_param(ParamClause::Where, value);
+        self.where_clauses.push(format!("LOWER({}) = LOWER({})", column, placeholder));
+        self
+    }
+
+    fn where_between(&mut self, column: &str, low: QueryValue, high: QueryValue) -> &mut Self {
+        self.where_columns.push(column.to_string());
+        let low = self.add_param(ParamClause::Where, low);
+        let high = self.add_param(ParamClause::Where, high);
+        self.where_clauses.push(format!("{} BETWEEN {} AND {}", column, low, high));
+        self
+    }
+
+    fn where_not_between(&mut self, column: &str, low: QueryValue, high: QueryValue) -> &mut Self {
+        self.where_columns.push(column.to_string());
+        let low = self.add_param(ParamClause::Where, low);
+        let high = self.add_param(ParamClause::Where, high);
+        self.where_clauses.push(format!("{} NOT BETWEEN {} AND {}", column, low, high));
+        self
+    }
+
+    fn where_json_eq(&mut self, column: &str, path: &str, value: QueryValue) -> &mut Self {
+        self.where_columns.push(column.to_string());
+        let extract = match self.dialect {
+            Dialect::SQLite => {
+                let path = self.add_param(ParamClause::Where, QueryValue::String(format!("$.{}", path)));
+                format!("json_extract({}, {})", column, path)
+            }
+            Dialect::MySQL => {
+                let path = self.add_param(ParamClause::Where, QueryValue::String(format!("$.{}", path)));
+                format!("JSON_UNQUOTE(JSON_EXTRACT({}, {}))", column, path)
+            }
+            Dialect::Postgres => {
+                let keys: Vec<String> = path
+                    .split('.')
+                    .map(|key| self.add_param(ParamClause::Where, QueryValue::from(key)))
+                    .collect();
+                format!("jsonb_extract_path_text({}, {})", column, keys.join(", "))
+            }
+        };
+        let placeholder = self.add_param(ParamClause::Where, value);
+        self.where_clauses.push(format!("{} = {}", extract, placeholder));
+        self
+    }
+
+    fn where_match(&mut self, columns: &[&str], query: QueryValue) -> &mut Self {
+        self.where_columns.extend(columns.iter().map(|c| c.to_string()));
+        let condition = match self.dialect {
+            Dialect::SQLite => {
+                let matches: Vec<String> = columns
+                    .iter()
+                    .map(|column| format!("{} MATCH {}", column, self.add_param(ParamClause::Where, query.clone())))
+                    .collect();
+                format!("({})", matches.join(" OR "))
+            }
+            Dialect::MySQL => {
+                let placeholder = self.add_param(ParamClause::Where, query);
+                format!("MATCH ({}) AGAINST ({})", columns.join(", "), placeholder)
+            }
+            Dialect::Postgres => {
+                let placeholder = self.add_param(ParamClause::Where, query);
+                format!(
+                    "to_tsvector(concat_ws(' ', {})) @@ plainto_tsquery({})",
+                    columns.join(", "),
+                    placeholder
+                )
+            }
+        };
+        self.where_clauses.push(condition);
+        self
+    }
+
     fn order_by(&mut self, column: &str, direction: OrderDirection) -> &mut Self {
         self.order_by.push((column.to_string(), direction));
+        self.order_by_columns.push(column.to_string());
+        self
+    }
+
+    fn order_by_raw(&mut self, expression: &str, direction: OrderDirection) -> &mut Self {
+        self.order_by.push((expression.to_string(), direction));
         self
     }
 
@@ -296,7 +839,13 @@ impl QueryBuilder for QueryBuilderEnum {
     }
 
     fn values_params(&mut self, values: &[QueryValue]) -> &mut Self {
-        let value_row: Vec<String> = values.iter().map(|v| self.add_param(v.clone())).collect();
+        let value_row: Vec<String> = values.iter().map(|v| self.add_param(ParamClause::Set, v.clone())).collect();
+        self.insert_values.push(value_row);
+        self
+    }
+
+    fn values_owned(&mut self, values: Vec<QueryValue>) -> &mut Self {
+        let value_row: Vec<String> = values.into_iter().map(|v| self.add_param(ParamClause::Set, v)).collect();
         self.insert_values.push(value_row);
         self
     }
@@ -313,7 +862,7 @@ impl QueryBuilder for QueryBuilderEnum {
     }
 
     fn set_param(&mut self, column: &str, value: QueryValue) -> &mut Self {
-        let placeholder = self.add_param(value);
+        let placeholder = self.add_param(ParamClause::Set, value);
         self.update_sets.push((column.to_string(), placeholder));
         self
     }
@@ -324,9 +873,17 @@ impl QueryBuilder for QueryBuilderEnum {
         self
     }
 
+    fn on_conflict_update(&mut self, conflict_columns: &[&str], update_columns: &[&str]) -> &mut Self {
+        self.on_conflict = Some((
+            conflict_columns.iter().map(|c| c.to_string()).collect(),
+            update_columns.iter().map(|c| c.to_string()).collect(),
+        ));
+        self
+    }
+
     fn returning(&mut self, columns: &[&str]) -> &mut Self {
-        // Only store if SQLite, silently ignore for MySQL
-        if self.dialect == Dialect::SQLite {
+        // Silently ignore for MySQL, which has no RETURNING
+        if self.dialect.supports_returning() {
             self.returning_columns = columns.iter().map(|c| c.to_string()).collect();
         }
         self
@@ -337,13 +894,62 @@ impl QueryBuilder for QueryBuilderEnum {
         self
     }
 
+    fn join_on_params(&mut self, table: &str, on: &str, params: &[QueryValue], join_type: JoinType) -> &mut Self {
+        let on = self.add_condition(ParamClause::Join, on, params);
+        self.joins.push((join_type, table.to_string(), on));
+        self
+    }
+
     fn group_by(&mut self, columns: &[&str]) -> &mut Self {
         self.group_by_columns = columns.iter().map(|c| c.to_string()).collect();
         self
     }
 
     fn having(&mut self, condition: &str) -> &mut Self {
-        self.having_clause = Some(condition.to_string());
+        self.clear_having();
+        self.having_clauses.push(condition.to_string());
+        self
+    }
+
+    fn and_having(&mut self, condition: &str) -> &mut Self {
+        self.having_clauses.push(condition.to_string());
+        self
+    }
+
+    fn having_params(&mut self, condition: &str, params: &[QueryValue]) -> &mut Self {
+        let condition = self.add_condition(ParamClause::Having, condition, params);
+        self.having_clauses.push(condition);
+        self
+    }
+
+    fn select_window(
+        &mut self,
+        alias: &str,
+        func: &str,
+        partition_by: &[&str],
+        order_by: &[(&str, OrderDirection)],
+    ) -> &mut Self {
+        let expr = format!("{} AS {}", window(func, partition_by, order_by), alias);
+        self.query_type = QueryType::Select;
+        self.columns.push(Column::new(expr, ColumnType::BigInteger));
+        self
+    }
+
+    fn select_aggregate(&mut self, alias: &str, func: &str, column: &str, distinct: bool) -> &mut Self {
+        let expr = format!("{} AS {}", aggregate(func, column, distinct), alias);
+        self.query_type = QueryType::Select;
+        self.columns.push(Column::new(expr, ColumnType::BigInteger));
+        self
+    }
+
+    fn select_related_count(&mut self, alias: &str, table: &str, foreign_key: &str, local_key: &str) -> &mut Self {
+        let expr = format!(
+            "(SELECT COUNT(*) FROM {} WHERE {}.{} = {}) AS {}",
+            table, table, foreign_key, local_key, alias
+        );
+        self.query_type = QueryType::Select;
+        self.columns.push(Column::new(expr, ColumnType::BigInteger));
+        self.related_counts.push([alias, table, foreign_key, local_key].map(str::to_string));
         self
     }
 
@@ -353,24 +959,62 @@ impl QueryBuilder for QueryBuilderEnum {
     }
 
     fn build(&self) -> Result<String> {
-        match self.query_type {
+        self.validate_identifiers()?;
+        self.check_operators()?;
+        self.check_named_params()?;
+        self.check_placeholders()?;
+        self.check_full_table()?;
+        self.check_group_by()?;
+        let sql = match self.query_type {
             QueryType::Select => self.build_select(),
             QueryType::Insert => self.build_insert(),
             QueryType::Update => self.build_update(),
             QueryType::Delete => self.build_delete(),
-        }
+        }?;
+        Ok(self.dialect.number_placeholders(&sql))
     }
 
     fn params(&self) -> &[QueryValue] {
         &self.params
     }
 
+    fn to_count_sql(&self) -> Result<(String, Vec<QueryValue>)> {
+        if self.query_type != QueryType::Select {
+            return Err(crate::error::Error::QueryError(
+                "Only a SELECT can be turned into a count query".to_string(),
+            ));
+        }
+
+        let mut count = self.clone();
+        count.order_by.clear();
+        count.order_by_columns.clear();
+        count.limit = None;
+        count.offset = None;
+
+        if count.is_distinct {
+            let (sql, params) = count.build_with_params()?;
+            return Ok((format!("SELECT COUNT(*) AS count FROM ({}) AS counted", sql), params));
+        }
+
+        if count.group_by_columns.is_empty() {
+            count.columns = vec![Column::new("COUNT(*) AS count", ColumnType::BigInteger)];
+        } else {
+            count.columns = vec![Column::new("COUNT(*) OVER () AS count", ColumnType::BigInteger)];
+            count.limit = Some(1);
+        }
+        count.build_with_params()
+    }
+
     fn reset(&mut self) {
         self.query_type = QueryType::Select;
         self.columns.clear();
         self.table = None;
         self.where_clauses.clear();
+        self.or_where_indices.clear();
+        self.where_columns.clear();
+        self.where_operators.clear();
         self.order_by.clear();
+        self.order_by_columns.clear();
         self.limit = None;
         self.offset = None;
         self.insert_table = None;
@@ -380,14 +1024,84 @@ impl QueryBuilder for QueryBuilderEnum {
         self.update_sets.clear();
         self.delete_table = None;
         self.returning_columns.clear();
+        self.on_conflict = None;
         self.joins.clear();
+        self.related_counts.clear();
         self.group_by_columns.clear();
-        self.having_clause = None;
+        self.having_clauses.clear();
         self.is_distinct = false;
         self.params.clear();
+        self.param_names.clear();
+        self.param_counts = [0; 4];
+        self.named_params.clear();
+        self.trusted_identifiers = false;
+        self.safe_mode = true;
+        self.strict_group_by = false;
+        self.placeholder_mismatches.clear();
     }
 }
 
+/// Join WHERE conditions with AND, or with OR before the conditions at `or_indices`
+fn join_conditions(clauses: &[String], or_indices: &[usize]) -> String {
+    let mut sql = String::new();
+    for (i, clause) in clauses.iter().enumerate() {
+        if i > 0 {
+            sql.push_str(if or_indices.contains(&i) { " OR " } else { " AND " });
+        }
+        sql.push_str(clause);
+    }
+    sql
+}
+
+/// Split a table reference (`orders`, `orders o`, `orders AS o`) into its name and alias
+fn split_table_alias(reference: &str) -> (&str, Option<&str>) {
+    let parts: Vec<&str> = reference.split_whitespace().collect();
+    match parts.as_slice() {
+        [name, alias] => (name, Some(alias)),
+        [name, keyword, alias] if keyword.eq_ignore_ascii_case("as") => (name, Some(alias)),
+        _ => (reference.trim(), None),
+    }
+}
+
+/// Rewrite `:name` markers (outside string literals) to `?`, returning the new
+/// condition and every placeholder in order (`None` for `?`, `Some(name)` for `:name`)
+fn split_named_params(condition: &str) -> (String, Vec<Option<String>>) {
+    let mut result = String::with_capacity(condition.len());
+    let mut markers = Vec::new();
+    let mut in_string = false;
+    let mut chars = condition.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\'' => {
+                in_string = !in_string;
+                result.push(ch);
+            }
+            '?' if !in_string => {
+                markers.push(None);
+                result.push(ch);
+            }
+            // Leave Postgres `::type` casts alone
+            ':' if !in_string && chars.peek() == Some(&':') => {
+                result.push(ch);
+                result.push(chars.next().unwrap_or(':'));
+            }
+            ':' if !in_string && chars.peek().is_some_and(|c| c.is_ascii_alphabetic() || *c == '_') => {
+                let mut name = String::new();
+                while let Some(c) = chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '_') {
+                    name.push(c);
+                }
+                markers.push(Some(name));
+                result.push('?');
+            }
+            _ => result.push(ch),
+        }
+    }
+
+    (result, markers)
+}
+
 // Type aliases for backward compatibility
 pub type SQLiteQueryBuilder = QueryBuilderEnum;
-pub type MySQLQueryBuilder = QueryBuilderEnum;
\ No newline at end of file
+pub type MySQLQueryBuilder = QueryBuilderEnum;
+pub type PostgresQueryBuilder = QueryBuilderEnum;
\ No newline at end of file
@@ -1,23 +1,156 @@
+use crate::backend::{ensure_pool_open, map_pool_error};
 use crate::error::{Error, Result};
+use crate::model::Value;
+use crate::utils;
+use serde::{Deserialize, Serialize};
 use serde_json;
-use sqlx::{AnyPool, Column, Row};
+use sqlx::{MySqlPool, SqlitePool};
 
 /// Value type for query parameters
-#[derive(Debug, Clone)]
+///
+/// Serializes externally tagged (`{"I64": 1}`, `"Null"`) so `I32` and `I64`
+/// survive a round trip, e.g. as a cache key or in an audit log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum QueryValue {
     Null,
     Bool(bool),
+    I8(i8),
+    I16(i16),
     I32(i32),
     I64(i64),
     F64(f64),
     String(String),
+    /// Bound as a native array on Postgres and as a JSON array elsewhere
+    Array(Vec<QueryValue>),
+}
+
+impl From<bool> for QueryValue {
+    fn from(v: bool) -> Self {
+        QueryValue::Bool(v)
+    }
+}
+
+impl From<i8> for QueryValue {
+    fn from(v: i8) -> Self {
+        QueryValue::I8(v)
+    }
+}
+
+impl From<i16> for QueryValue {
+    fn from(v: i16) -> Self {
+        QueryValue::I16(v)
+    }
+}
+
+impl From<i32> for QueryValue {
+    fn from(v: i32) -> Self {
+        QueryValue::I32(v)
+    }
+}
+
+impl From<i64> for QueryValue {
+    fn from(v: i64) -> Self {
+        QueryValue::I64(v)
+    }
+}
+
+impl From<f64> for QueryValue {
+    fn from(v: f64) -> Self {
+        QueryValue::F64(v)
+    }
+}
+
+impl From<String> for QueryValue {
+    fn from(v: String) -> Self {
+        QueryValue::String(v)
+    }
+}
+
+impl From<&str> for QueryValue {
+    fn from(v: &str) -> Self {
+        QueryValue::String(v.to_string())
+    }
+}
+
+impl<T: Into<QueryValue>> From<Vec<T>> for QueryValue {
+    fn from(v: Vec<T>) -> Self {
+        QueryValue::Array(v.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<QueryValue>> From<Option<T>> for QueryValue {
+    fn from(v: Option<T>) -> Self {
+        v.map(Into::into).unwrap_or(QueryValue::Null)
+    }
+}
+
+impl From<Value> for QueryValue {
+    fn from(v: Value) -> Self {
+        match v {
+            Value::Null => QueryValue::Null,
+            Value::Bool(b) => QueryValue::Bool(b),
+            Value::I32(n) => QueryValue::I32(n),
+            Value::I64(n) => QueryValue::I64(n),
+            Value::F64(n) => QueryValue::F64(n),
+            Value::String(s) => QueryValue::String(s),
+            Value::Json(v) => QueryValue::String(v.to_string()),
+        }
+    }
+}
+
+impl QueryValue {
+    /// Render as a quoted SQL literal (for debugging output, not execution)
+    pub fn to_sql_literal(&self) -> String {
+        match self {
+            QueryValue::Null => "NULL".to_string(),
+            QueryValue::Bool(b) => if *b { "TRUE" } else { "FALSE" }.to_string(),
+            QueryValue::I8(n) => n.to_string(),
+            QueryValue::I16(n) => n.to_string(),
+            QueryValue::I32(n) => n.to_string(),
+            QueryValue::I64(n) => n.to_string(),
+            QueryValue::F64(n) => n.to_string(),
+            QueryValue::String(s) => format!("'{}'", s.replace('\'', "''")),
+            QueryValue::Array(values) => {
+                let items: Vec<String> = values.iter().map(QueryValue::to_sql_literal).collect();
+                format!("ARRAY[{}]", items.join(", "))
+            }
+        }
+    }
+
+    /// Convert to a JSON value, used to store arrays where there is no native array type
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            QueryValue::Null => serde_json::Value::Null,
+            QueryValue::Bool(b) => serde_json::Value::from(*b),
+            QueryValue::I8(n) => serde_json::Value::from(*n),
+            QueryValue::I16(n) => serde_json::Value::from(*n),
+            QueryValue::I32(n) => serde_json::Value::from(*n),
+            QueryValue::I64(n) => serde_json::Value::from(*n),
+            QueryValue::F64(n) => serde_json::Value::from(*n),
+            QueryValue::String(s) => serde_json::Value::from(s.as_str()),
+            QueryValue::Array(values) => values.iter().map(QueryValue::to_json).collect(),
+        }
+    }
+}
+
+impl std::fmt::Display for QueryValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_sql_literal())
+    }
+}
+
+/// Connection pool an executor runs against
+#[derive(Clone)]
+pub enum ExecutorPool {
+    SQLite(SqlitePool),
+    MySQL(MySqlPool),
 }
 
 /// Query executor for running built queries
 pub struct QueryExecutor {
     sql: String,
     params: Vec<QueryValue>,
-    pool: Option<AnyPool>,
+    pool: Option<ExecutorPool>,
 }
 
 impl QueryExecutor {
@@ -30,7 +163,7 @@ impl QueryExecutor {
     }
 
     /// Create a new executor with a connection pool
-    pub fn with_pool(sql: String, pool: AnyPool) -> Self {
+    pub fn with_pool(sql: String, pool: ExecutorPool) -> Self {
         Self {
             sql,
             params: Vec::new(),
@@ -48,74 +181,64 @@ impl QueryExecutor {
         &self.sql
     }
 
-    /// Helper to bind all parameters to a query
-    fn bind_params<'q>(&'q self, mut query: sqlx::query::Query<'q, sqlx::Any, sqlx::any::AnyArguments<'q>>) -> sqlx::query::Query<'q, sqlx::Any, sqlx::any::AnyArguments<'q>> {
-        for param in &self.params {
-            query = match param {
-                QueryValue::Null => query.bind(Option::<i32>::None),
-                QueryValue::Bool(v) => query.bind(*v),
-                QueryValue::I32(v) => query.bind(*v),
-                QueryValue::I64(v) => query.bind(*v),
-                QueryValue::F64(v) => query.bind(*v),
-                QueryValue::String(v) => query.bind(v.as_str()),
-            };
-        }
-        query
-    }
-
-    /// Helper to convert a row to JSON
-    fn row_to_json(row: &sqlx::any::AnyRow) -> serde_json::Value {
-        let mut obj = serde_json::Map::new();
-        for (i, column) in row.columns().iter().enumerate() {
-            let column_name = column.name();
-            let value = if let Ok(v) = row.try_get::<i64, _>(i) {
-                serde_json::json!(v)
-            } else if let Ok(v) = row.try_get::<f64, _>(i) {
-                serde_json::json!(v)
-            } else if let Ok(v) = row.try_get::<bool, _>(i) {
-                serde_json::Value::Bool(v)
-            } else if let Ok(v) = row.try_get::<String, _>(i) {
-                serde_json::Value::String(v)
-            } else {
-                serde_json::Value::Null
-            };
-            obj.insert(column_name.to_string(), value);
+    /// The pool to run against, failing with a connection error once it has been closed
+    fn pool(&self) -> Result<&ExecutorPool> {
+        let pool = self.pool.as_ref().ok_or_else(|| {
+            Error::QueryError("No connection pool available".to_string())
+        })?;
+        match pool {
+            ExecutorPool::SQLite(pool) => ensure_pool_open(pool)?,
+            ExecutorPool::MySQL(pool) => ensure_pool_open(pool)?,
         }
-        serde_json::Value::Object(obj)
+        Ok(pool)
     }
 
     /// Execute a SELECT query and return results as JSON values
     pub async fn fetch_all(&self) -> Result<Vec<serde_json::Value>> {
-        let pool = self.pool.as_ref().ok_or_else(|| {
-            Error::QueryError("No connection pool available".to_string())
-        })?;
-
-        let query = self.bind_params(sqlx::query(&self.sql));
-        let rows = query.fetch_all(pool).await?;
-
-        Ok(rows.iter().map(Self::row_to_json).collect())
+        let rows = match self.pool()? {
+            ExecutorPool::SQLite(pool) => {
+                let query = utils::bind_sqlite_params(sqlx::query(&self.sql), &self.params);
+                let rows = query.fetch_all(pool).await.map_err(map_pool_error(pool))?;
+                rows.iter().map(utils::sqlite_row_to_json).collect()
+            }
+            ExecutorPool::MySQL(pool) => {
+                let query = utils::bind_mysql_params(sqlx::query(&self.sql), &self.params);
+                let rows = query.fetch_all(pool).await.map_err(map_pool_error(pool))?;
+                rows.iter().map(utils::mysql_row_to_json).collect()
+            }
+        };
+        Ok(rows)
     }
 
     /// Execute a SELECT query and return a single result
     pub async fn fetch_one(&self) -> Result<Option<serde_json::Value>> {
-        let pool = self.pool.as_ref().ok_or_else(|| {
-            Error::QueryError("No connection pool available".to_string())
-        })?;
-
-        let query = self.bind_params(sqlx::query(&self.sql));
-        let row = query.fetch_optional(pool).await?;
-
-        Ok(row.as_ref().map(Self::row_to_json))
+        let row = match self.pool()? {
+            ExecutorPool::SQLite(pool) => {
+                let query = utils::bind_sqlite_params(sqlx::query(&self.sql), &self.params);
+                let row = query.fetch_optional(pool).await.map_err(map_pool_error(pool))?;
+                row.as_ref().map(utils::sqlite_row_to_json)
+            }
+            ExecutorPool::MySQL(pool) => {
+                let query = utils::bind_mysql_params(sqlx::query(&self.sql), &self.params);
+                let row = query.fetch_optional(pool).await.map_err(map_pool_error(pool))?;
+                row.as_ref().map(utils::mysql_row_to_json)
+            }
+        };
+        Ok(row)
     }
 
     /// Execute INSERT/UPDATE/DELETE and return affected rows
     pub async fn execute(&self) -> Result<u64> {
-        let pool = self.pool.as_ref().ok_or_else(|| {
-            Error::QueryError("No connection pool available".to_string())
-        })?;
-
-        let query = self.bind_params(sqlx::query(&self.sql));
-        let result = query.execute(pool).await?;
-        Ok(result.rows_affected())
+        let rows_affected = match self.pool()? {
+            ExecutorPool::SQLite(pool) => {
+                let query = utils::bind_sqlite_params(sqlx::query(&self.sql), &self.params);
+                query.execute(pool).await.map_err(map_pool_error(pool))?.rows_affected()
+            }
+            ExecutorPool::MySQL(pool) => {
+                let query = utils::bind_mysql_params(sqlx::query(&self.sql), &self.params);
+                query.execute(pool).await.map_err(map_pool_error(pool))?.rows_affected()
+            }
+        };
+        Ok(rows_affected)
     }
-}
\ No newline at end of file
+}
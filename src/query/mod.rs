@@ -4,7 +4,7 @@ pub mod executor;
 use crate::error::Result;
 use crate::schema::Column;
 
-pub use executor::{QueryExecutor, QueryValue};
+pub use executor::{ExecutorPool, QueryExecutor, QueryValue};
 
 /// Trait for building SQL queries
 pub trait QueryBuilder: Send + Sync {
@@ -18,18 +18,90 @@ pub trait QueryBuilder: Send + Sync {
     #[deprecated(note = "Use where_eq() with parameters for SQL injection protection")]
     fn where_clause(&mut self, condition: &str) -> &mut Self;
     
+    /// Add a WHERE condition whose `:name` placeholders are bound with `bind_named`
+    ///
+    /// No values are passed here, so a positional `?` fails the build.
+    fn where_raw(&mut self, condition: &str) -> &mut Self;
+
+    /// Bind a value to every `:name` placeholder with this name, before or after it is used
+    fn bind_named(&mut self, name: &str, value: QueryValue) -> &mut Self;
+
     /// Add a WHERE clause with a parameter (safe from SQL injection)
     fn where_eq(&mut self, column: &str, value: QueryValue) -> &mut Self;
     
+    /// Add a `column IN (?, ...)` WHERE clause; an empty list renders `1 = 0` and matches no rows
+    fn where_in(&mut self, column: &str, values: &[QueryValue]) -> &mut Self;
+
+    /// OR a `column = ?` condition onto the WHERE clause
+    ///
+    /// WHERE conditions keep SQL precedence: AND binds tighter than OR, so each
+    /// `or_*` call starts a new top-level term and later `where_*` calls are ANDed
+    /// into that term. `where_eq(a).where_eq(b).or_where_eq(c)` renders
+    /// `a = ? AND b = ? OR c = ?`, i.e. `(a AND b) OR c`. Use `where_group` to
+    /// parenthesize anything else.
+    fn or_where_eq(&mut self, column: &str, value: QueryValue) -> &mut Self;
+
+    /// OR a `column IN (?, ...)` condition onto the WHERE clause (see `or_where_eq`)
+    fn or_where_in(&mut self, column: &str, values: &[QueryValue]) -> &mut Self;
+
+    /// AND a parenthesized group of the WHERE conditions added by `f`
+    fn where_group<F: FnOnce(&mut Self)>(&mut self, f: F) -> &mut Self;
+
+    /// OR a parenthesized group of the WHERE conditions added by `f`
+    fn or_where_group<F: FnOnce(&mut Self)>(&mut self, f: F) -> &mut Self;
+
+    /// Add a `column <op> ?` WHERE clause, where `op` is one of `=`, `<>`, `!=`, `<`, `<=`, `>`, `>=`
+    fn where_cmp(&mut self, column: &str, op: &str, value: QueryValue) -> &mut Self;
+
+    /// Add a case-insensitive `LOWER(column) = LOWER(?)` WHERE clause
+    ///
+    /// Wrapping the column in LOWER() prevents use of a plain index on it;
+    /// add a functional index on `LOWER(column)` for large tables.
+    fn where_eq_ci(&mut self, column: &str, value: QueryValue) -> &mut Self;
+    
+    /// Add a `column BETWEEN ? AND ?` WHERE clause (inclusive)
+    fn where_between(&mut self, column: &str, low: QueryValue, high: QueryValue) -> &mut Self;
+
+    /// Add a `column NOT BETWEEN ? AND ?` WHERE clause
+    fn where_not_between(&mut self, column: &str, low: QueryValue, high: QueryValue) -> &mut Self;
+    
+    /// Add a WHERE clause comparing a value inside a JSON column, where `path` is a
+    /// dot-separated key path such as `profile.role`
+    ///
+    /// Renders `json_extract(col, ?)` on SQLite, `JSON_UNQUOTE(JSON_EXTRACT(col, ?))` on
+    /// MySQL and `jsonb_extract_path_text(col, ?, ...)` on Postgres, with the path bound.
+    fn where_json_eq(&mut self, column: &str, path: &str, value: QueryValue) -> &mut Self;
+    
+    /// Add a full-text search WHERE clause over the given columns
+    ///
+    /// Renders `col MATCH ?` (OR-ed per column) on SQLite, which requires the table to be
+    /// an FTS5 virtual table; `MATCH (cols) AGAINST (?)` on MySQL, which requires a
+    /// FULLTEXT index covering exactly these columns; and a `to_tsvector @@ plainto_tsquery`
+    /// predicate on Postgres.
+    fn where_match(&mut self, columns: &[&str], query: QueryValue) -> &mut Self;
+    
     /// Build an ORDER BY clause
     fn order_by(&mut self, column: &str, direction: OrderDirection) -> &mut Self;
+
+    /// Add ORDER BY on a trusted expression such as `LENGTH(name)`, which is not validated
+    fn order_by_raw(&mut self, expression: &str, direction: OrderDirection) -> &mut Self;
     
     /// Build a LIMIT clause
     fn limit(&mut self, limit: u64) -> &mut Self;
     
     /// Build an OFFSET clause
     fn offset(&mut self, offset: u64) -> &mut Self;
+
+    /// Set LIMIT and OFFSET for a 1-based page of `size` rows
+    fn page(&mut self, number: u64, size: u64) -> &mut Self {
+        self.limit(size).offset(number.saturating_sub(1) * size)
+    }
     
+    /// Set LIMIT and OFFSET together
+    fn limit_offset(&mut self, limit: u64, offset: u64) -> &mut Self {
+        self.limit(limit).offset(offset)
+    }
+
     /// Build an INSERT query
     fn insert_into(&mut self, table: &str, columns: &[&str]) -> &mut Self;
     
@@ -39,6 +111,9 @@ pub trait QueryBuilder: Send + Sync {
     
     /// Add parameterized values for INSERT (safe from SQL injection)
     fn values_params(&mut self, values: &[QueryValue]) -> &mut Self;
+
+    /// Add parameterized values for INSERT, taking ownership instead of cloning each value
+    fn values_owned(&mut self, values: Vec<QueryValue>) -> &mut Self;
     
     /// Build an UPDATE query
     fn update(&mut self, table: &str) -> &mut Self;
@@ -53,12 +128,22 @@ pub trait QueryBuilder: Send + Sync {
     /// Build a DELETE query
     fn delete_from(&mut self, table: &str) -> &mut Self;
     
+    /// Turn the INSERT into an upsert: on a conflict over `conflict_columns`, overwrite
+    /// `update_columns` with the new values (or do nothing if there are none)
+    ///
+    /// MySQL ignores `conflict_columns` and resolves conflicts on any unique key.
+    fn on_conflict_update(&mut self, conflict_columns: &[&str], update_columns: &[&str]) -> &mut Self;
+
     /// Add RETURNING clause (SQLite only)
     fn returning(&mut self, columns: &[&str]) -> &mut Self;
     
-    /// Add JOIN clause
+    /// Add JOIN clause (also applies to UPDATE/DELETE on MySQL)
     fn join(&mut self, table: &str, on: &str, join_type: JoinType) -> &mut Self;
     
+    /// Add JOIN clause whose ON condition has `?` placeholders bound to the given parameters
+    /// and `:name` placeholders bound with `bind_named`
+    fn join_on_params(&mut self, table: &str, on: &str, params: &[QueryValue], join_type: JoinType) -> &mut Self;
+
     /// Add INNER JOIN clause
     fn inner_join(&mut self, table: &str, on: &str) -> &mut Self {
         self.join(table, on, JoinType::Inner)
@@ -77,9 +162,42 @@ pub trait QueryBuilder: Send + Sync {
     /// Add GROUP BY clause
     fn group_by(&mut self, columns: &[&str]) -> &mut Self;
     
-    /// Add HAVING clause
+    /// Set the HAVING clause, replacing any HAVING conditions added so far
     fn having(&mut self, condition: &str) -> &mut Self;
+
+    /// AND a condition onto the HAVING clause
+    fn and_having(&mut self, condition: &str) -> &mut Self;
+
+    /// AND a HAVING condition with `?` placeholders bound to the given parameters
+    /// and `:name` placeholders bound with `bind_named`
+    fn having_params(&mut self, condition: &str, params: &[QueryValue]) -> &mut Self;
+
+    /// Add a HAVING `expr = ?` condition
+    fn having_eq(&mut self, expr: &str, value: QueryValue) -> &mut Self {
+        self.having_params(&format!("{} = ?", expr), &[value])
+    }
+
+    /// Add a HAVING `expr > ?` condition
+    fn having_gt(&mut self, expr: &str, value: QueryValue) -> &mut Self {
+        self.having_params(&format!("{} > ?", expr), &[value])
+    }
     
+    /// Add a window function column, e.g. `ROW_NUMBER() OVER (PARTITION BY ... ORDER BY ...) AS alias`
+    fn select_window(
+        &mut self,
+        alias: &str,
+        func: &str,
+        partition_by: &[&str],
+        order_by: &[(&str, OrderDirection)],
+    ) -> &mut Self;
+
+    /// Add an aggregate column `FUNC([DISTINCT] column) AS alias`
+    fn select_aggregate(&mut self, alias: &str, func: &str, column: &str, distinct: bool) -> &mut Self;
+
+    /// Add a correlated count column
+    /// `(SELECT COUNT(*) FROM table WHERE table.foreign_key = local_key) AS alias`
+    fn select_related_count(&mut self, alias: &str, table: &str, foreign_key: &str, local_key: &str) -> &mut Self;
+
     /// Add DISTINCT
     fn distinct(&mut self) -> &mut Self;
     
@@ -88,11 +206,98 @@ pub trait QueryBuilder: Send + Sync {
     
     /// Get the query parameters
     fn params(&self) -> &[QueryValue];
+
+    /// Build the final SQL string together with an owned copy of its parameters
+    fn build_with_params(&self) -> Result<(String, Vec<QueryValue>)> {
+        Ok((self.build()?, self.params().to_vec()))
+    }
+
+    /// Build a query counting the rows this SELECT would return, as a `count` column,
+    /// keeping its FROM/JOIN/WHERE/GROUP BY/HAVING and parameters
+    ///
+    /// ORDER BY, LIMIT and OFFSET are dropped. A grouped select counts its groups
+    /// with `COUNT(*) OVER ()` and returns no row when there are none; a DISTINCT
+    /// select is counted as a subquery.
+    fn to_count_sql(&self) -> Result<(String, Vec<QueryValue>)>;
+
+    /// Render the SQL with parameters interpolated, for logging/debugging only (never execute it)
+    fn to_debug_sql(&self) -> String {
+        match self.build() {
+            Ok(sql) => interpolate_params(&sql, self.params()),
+            Err(e) => format!("/* {} */", e),
+        }
+    }
     
     /// Reset the query builder
     fn reset(&mut self);
 }
 
+/// Substitute each `?` or `$n` placeholder (outside string literals) with a quoted parameter
+fn interpolate_params(sql: &str, params: &[QueryValue]) -> String {
+    let mut result = String::with_capacity(sql.len());
+    let mut next_param = params.iter();
+    let mut in_string = false;
+    let mut chars = sql.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\'' => {
+                in_string = !in_string;
+                result.push(ch);
+            }
+            '?' if !in_string => match next_param.next() {
+                Some(param) => result.push_str(&param.to_sql_literal()),
+                None => result.push(ch),
+            },
+            '$' if !in_string && chars.peek().is_some_and(|c| c.is_ascii_digit()) => {
+                let mut digits = String::new();
+                while let Some(digit) = chars.next_if(|c| c.is_ascii_digit()) {
+                    digits.push(digit);
+                }
+                let param = digits.parse::<usize>().ok().and_then(|n| params.get(n.checked_sub(1)?));
+                match param {
+                    Some(param) => result.push_str(&param.to_sql_literal()),
+                    None => {
+                        result.push(ch);
+                        result.push_str(&digits);
+                    }
+                }
+            }
+            _ => result.push(ch),
+        }
+    }
+
+    result
+}
+
+/// Render an aggregate expression, optionally over distinct values: `COUNT(DISTINCT col)`
+pub fn aggregate(func: &str, column: &str, distinct: bool) -> String {
+    if distinct {
+        format!("{}(DISTINCT {})", func, column)
+    } else {
+        format!("{}({})", func, column)
+    }
+}
+
+/// Render a window function expression: `func OVER (PARTITION BY ... ORDER BY ...)`
+pub fn window(func: &str, partition_by: &[&str], order_by: &[(&str, OrderDirection)]) -> String {
+    let mut over = Vec::new();
+
+    if !partition_by.is_empty() {
+        over.push(format!("PARTITION BY {}", partition_by.join(", ")));
+    }
+
+    if !order_by.is_empty() {
+        let order: Vec<String> = order_by
+            .iter()
+            .map(|(col, dir)| format!("{} {}", col, dir))
+            .collect();
+        over.push(format!("ORDER BY {}", order.join(", ")));
+    }
+
+    format!("{} OVER ({})", func, over.join(" "))
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum JoinType {
     Inner,
@@ -101,8 +306,9 @@ pub enum JoinType {
     Full,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum OrderDirection {
+    #[default]
     Asc,
     Desc,
 }
@@ -114,4 +320,17 @@ impl std::fmt::Display for OrderDirection {
             OrderDirection::Desc => write!(f, "DESC"),
         }
     }
+}
+
+impl std::str::FromStr for OrderDirection {
+    type Err = crate::error::Error;
+
+    /// Parse `asc` or `desc` in any case, e.g. from a `?sort=name:desc` query param
+    fn from_str(s: &str) -> crate::error::Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "asc" => Ok(OrderDirection::Asc),
+            "desc" => Ok(OrderDirection::Desc),
+            _ => Err(crate::error::Error::QueryError(format!("Invalid order direction: {}", s))),
+        }
+    }
 }
\ No newline at end of file
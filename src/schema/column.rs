@@ -8,6 +8,8 @@ pub struct Column {
     unique: bool,
     primary_key: bool,
     auto_increment: bool,
+    check: Option<String>,
+    comment: Option<String>,
 }
 
 impl Column {
@@ -20,6 +22,8 @@ impl Column {
             unique: false,
             primary_key: false,
             auto_increment: false,
+            check: None,
+            comment: None,
         }
     }
 
@@ -76,6 +80,28 @@ impl Column {
         self.auto_increment
     }
 
+    /// Add an inline `CHECK (expression)` constraint
+    pub fn check(mut self, expression: impl Into<String>) -> Self {
+        self.check = Some(expression.into());
+        self
+    }
+
+    pub fn check_expression(&self) -> Option<&str> {
+        self.check.as_deref()
+    }
+
+    /// Document the column: `COMMENT '...'` on MySQL, an inline `/* ... */` on SQLite
+    /// (kept in `sqlite_master`), omitted on Postgres which only has `COMMENT ON`
+    pub fn comment(mut self, text: impl Into<String>) -> Self {
+        self.comment = Some(text.into());
+        self
+    }
+
+    /// The column's comment, if one was set with `comment`
+    pub fn comment_text(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+
     /// Generate SQL for this column definition
     pub fn to_sql(&self, dialect: crate::query::builder::Dialect) -> String {
         use crate::query::builder::Dialect;
@@ -90,6 +116,8 @@ impl Column {
             match dialect {
                 Dialect::SQLite => sql.push_str(" AUTOINCREMENT"),
                 Dialect::MySQL => sql.push_str(" AUTO_INCREMENT"),
+                // Postgres uses SERIAL/BIGSERIAL types instead
+                Dialect::Postgres => {}
             }
         }
         
@@ -105,23 +133,43 @@ impl Column {
             sql.push_str(&format!(" DEFAULT {}", default));
         }
         
+        if let Some(check) = &self.check {
+            sql.push_str(&format!(" CHECK ({})", check));
+        }
+
+        if let Some(comment) = &self.comment {
+            match dialect {
+                Dialect::MySQL => sql.push_str(&format!(" COMMENT '{}'", comment.replace('\'', "''"))),
+                Dialect::SQLite => sql.push_str(&format!(" /* {} */", comment.replace("*/", "* /"))),
+                Dialect::Postgres => {}
+            }
+        }
+        
         sql
     }
 
-    fn type_to_sql(&self, dialect: crate::query::builder::Dialect) -> String {
+    pub(crate) fn type_to_sql(&self, dialect: crate::query::builder::Dialect) -> String {
         use crate::query::builder::Dialect;
         
         match (&self.column_type, dialect) {
-            (ColumnType::Integer, Dialect::SQLite) => "INTEGER".to_string(),
+            // Postgres has no one-byte integer
+            (ColumnType::TinyInt, Dialect::Postgres) => "SMALLINT".to_string(),
+            (ColumnType::TinyInt, _) => "TINYINT".to_string(),
+            (ColumnType::SmallInt, _) => "SMALLINT".to_string(),
+            (ColumnType::Integer, Dialect::Postgres) if self.auto_increment => "SERIAL".to_string(),
+            (ColumnType::Integer, Dialect::SQLite | Dialect::Postgres) => "INTEGER".to_string(),
             (ColumnType::Integer, Dialect::MySQL) => "INT".to_string(),
             // SQLite uses INTEGER for primary keys with AUTOINCREMENT
             (ColumnType::BigInteger, Dialect::SQLite) if self.auto_increment => "INTEGER".to_string(),
+            (ColumnType::BigInteger, Dialect::Postgres) if self.auto_increment => "BIGSERIAL".to_string(),
             (ColumnType::BigInteger, _) => "BIGINT".to_string(),
             (ColumnType::Text, _) => "TEXT".to_string(),
             (ColumnType::Varchar(len), _) => format!("VARCHAR({})", len),
             (ColumnType::Boolean, Dialect::SQLite) => "INTEGER".to_string(),
-            (ColumnType::Boolean, Dialect::MySQL) => "BOOLEAN".to_string(),
+            (ColumnType::Boolean, Dialect::MySQL | Dialect::Postgres) => "BOOLEAN".to_string(),
+            (ColumnType::Float, Dialect::Postgres) => "REAL".to_string(),
             (ColumnType::Float, _) => "FLOAT".to_string(),
+            (ColumnType::Double, Dialect::Postgres) => "DOUBLE PRECISION".to_string(),
             (ColumnType::Double, _) => "DOUBLE".to_string(),
             (ColumnType::Decimal { precision, scale }, _) => {
                 format!("DECIMAL({}, {})", precision, scale)
@@ -129,18 +177,31 @@ impl Column {
             (ColumnType::Date, _) => "DATE".to_string(),
             (ColumnType::DateTime, Dialect::SQLite) => "TEXT".to_string(),
             (ColumnType::DateTime, Dialect::MySQL) => "DATETIME".to_string(),
+            (ColumnType::DateTime, Dialect::Postgres) => "TIMESTAMP".to_string(),
             (ColumnType::Timestamp, _) => "TIMESTAMP".to_string(),
             (ColumnType::Json, Dialect::SQLite) => "TEXT".to_string(),
             (ColumnType::Json, Dialect::MySQL) => "JSON".to_string(),
+            (ColumnType::Json, Dialect::Postgres) => "JSONB".to_string(),
             (ColumnType::Uuid, Dialect::SQLite) => "TEXT".to_string(),
             (ColumnType::Uuid, Dialect::MySQL) => "CHAR(36)".to_string(),
+            (ColumnType::Uuid, Dialect::Postgres) => "UUID".to_string(),
+            (ColumnType::Binary, Dialect::Postgres) => "BYTEA".to_string(),
             (ColumnType::Binary, _) => "BLOB".to_string(),
+            (ColumnType::Array(element), Dialect::Postgres) => {
+                let element = Column::new(self.name.clone(), (**element).clone());
+                format!("{}[]", element.type_to_sql(dialect))
+            }
+            // Arrays are stored as JSON where there is no native array type
+            (ColumnType::Array(_), Dialect::SQLite) => "TEXT".to_string(),
+            (ColumnType::Array(_), Dialect::MySQL) => "JSON".to_string(),
         }
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ColumnType {
+    TinyInt,
+    SmallInt,
     Integer,
     BigInteger,
     Text,
@@ -155,4 +216,49 @@ pub enum ColumnType {
     Json,
     Uuid,
     Binary,
-}
\ No newline at end of file
+    /// Native array on Postgres, JSON-encoded elsewhere
+    Array(Box<ColumnType>),
+}
+
+impl std::str::FromStr for ColumnType {
+    type Err = crate::error::Error;
+
+    /// Parse a type string such as `"varchar(255)"`, `"decimal(10,2)"` or `"integer[]"`
+    fn from_str(s: &str) -> crate::error::Result<Self> {
+        let unknown = || crate::error::Error::SerializationError(format!("Unknown column type: {}", s));
+        let lower = s.trim().to_lowercase();
+        if let Some(element) = lower.strip_suffix("[]") {
+            return element.parse().map(|element| ColumnType::Array(Box::new(element)));
+        }
+        let (name, args) = match lower.find('(') {
+            Some(open) => (&lower[..open], Some(lower[open + 1..].strip_suffix(')').ok_or_else(unknown)?)),
+            None => (lower.as_str(), None),
+        };
+
+        match (name.trim(), args) {
+            ("tinyint", None) => Ok(ColumnType::TinyInt),
+            ("smallint", None) => Ok(ColumnType::SmallInt),
+            ("integer", None) => Ok(ColumnType::Integer),
+            ("bigint", None) => Ok(ColumnType::BigInteger),
+            ("text", None) => Ok(ColumnType::Text),
+            ("varchar", Some(len)) => len.trim().parse().map(ColumnType::Varchar).map_err(|_| unknown()),
+            ("boolean", None) => Ok(ColumnType::Boolean),
+            ("float", None) => Ok(ColumnType::Float),
+            ("double", None) => Ok(ColumnType::Double),
+            ("decimal", Some(args)) => {
+                let (precision, scale) = args.split_once(',').ok_or_else(unknown)?;
+                Ok(ColumnType::Decimal {
+                    precision: precision.trim().parse().map_err(|_| unknown())?,
+                    scale: scale.trim().parse().map_err(|_| unknown())?,
+                })
+            }
+            ("date", None) => Ok(ColumnType::Date),
+            ("datetime", None) => Ok(ColumnType::DateTime),
+            ("timestamp", None) => Ok(ColumnType::Timestamp),
+            ("json", None) => Ok(ColumnType::Json),
+            ("uuid", None) => Ok(ColumnType::Uuid),
+            ("binary", None) => Ok(ColumnType::Binary),
+            _ => Err(unknown()),
+        }
+    }
+}
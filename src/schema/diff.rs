@@ -0,0 +1,168 @@
+use serde::{Deserialize, Serialize};
+use crate::schema::{ColumnSchema, ForeignKeySchema, SchemaExport, TableSchema};
+
+/// Differences between two exported schemas
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SchemaDiff {
+    pub added_tables: Vec<TableSchema>,
+    pub removed_tables: Vec<String>,
+    pub changed_tables: Vec<TableDiff>,
+}
+
+/// Differences within a table present in both schemas
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableDiff {
+    pub name: String,
+    pub added_columns: Vec<ColumnSchema>,
+    pub removed_columns: Vec<String>,
+    pub changed_columns: Vec<ColumnChange>,
+    pub added_foreign_keys: Vec<ForeignKeySchema>,
+    pub removed_foreign_keys: Vec<ForeignKeySchema>,
+}
+
+/// A column whose definition differs between two schemas
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnChange {
+    pub name: String,
+    pub from: ColumnSchema,
+    pub to: ColumnSchema,
+}
+
+impl SchemaDiff {
+    /// Check if the schemas are identical
+    pub fn is_empty(&self) -> bool {
+        self.added_tables.is_empty() && self.removed_tables.is_empty() && self.changed_tables.is_empty()
+    }
+}
+
+impl TableDiff {
+    fn is_empty(&self) -> bool {
+        self.added_columns.is_empty()
+            && self.removed_columns.is_empty()
+            && self.changed_columns.is_empty()
+            && self.added_foreign_keys.is_empty()
+            && self.removed_foreign_keys.is_empty()
+    }
+}
+
+impl SchemaExport {
+    /// Compute the changes needed to go from this schema to `other`
+    pub fn diff(&self, other: &SchemaExport) -> SchemaDiff {
+        let mut diff = SchemaDiff::default();
+
+        for table in &other.tables {
+            match self.tables.iter().find(|t| t.name == table.name) {
+                Some(current) => {
+                    let table_diff = diff_table(current, table);
+                    if !table_diff.is_empty() {
+                        diff.changed_tables.push(table_diff);
+                    }
+                }
+                None => diff.added_tables.push(table.clone()),
+            }
+        }
+
+        for table in &self.tables {
+            if !other.tables.iter().any(|t| t.name == table.name) {
+                diff.removed_tables.push(table.name.clone());
+            }
+        }
+
+        diff
+    }
+}
+
+fn diff_table(current: &TableSchema, desired: &TableSchema) -> TableDiff {
+    let mut diff = TableDiff {
+        name: desired.name.clone(),
+        added_columns: Vec::new(),
+        removed_columns: Vec::new(),
+        changed_columns: Vec::new(),
+        added_foreign_keys: Vec::new(),
+        removed_foreign_keys: Vec::new(),
+    };
+
+    for column in &desired.columns {
+        match current.columns.iter().find(|c| c.name == column.name) {
+            Some(existing) if existing != column => diff.changed_columns.push(ColumnChange {
+                name: column.name.clone(),
+                from: existing.clone(),
+                to: column.clone(),
+            }),
+            Some(_) => {}
+            None => diff.added_columns.push(column.clone()),
+        }
+    }
+
+    for column in &current.columns {
+        if !desired.columns.iter().any(|c| c.name == column.name) {
+            diff.removed_columns.push(column.name.clone());
+        }
+    }
+
+    for fk in &desired.foreign_keys {
+        if !current.foreign_keys.contains(fk) {
+            diff.added_foreign_keys.push(fk.clone());
+        }
+    }
+
+    for fk in &current.foreign_keys {
+        if !desired.foreign_keys.contains(fk) {
+            diff.removed_foreign_keys.push(fk.clone());
+        }
+    }
+
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::schema::{export_schema, Column, ColumnType, Table};
+
+    fn users_table() -> Table {
+        let mut table = Table::new("users");
+        table.add_column(Column::new("id", ColumnType::BigInteger).primary_key().auto_increment());
+        table.add_column(Column::new("email", ColumnType::Varchar(255)));
+        table
+    }
+
+    #[test]
+    fn test_diff_reports_added_column_and_dropped_table() {
+        let mut legacy = Table::new("legacy_sessions");
+        legacy.add_column(Column::new("token", ColumnType::Text));
+        let current = export_schema(vec![users_table(), legacy]);
+
+        let mut users = users_table();
+        users.add_column(Column::new("age", ColumnType::Integer).nullable(true));
+        let desired = export_schema(vec![users]);
+
+        let diff = current.diff(&desired);
+        assert!(diff.added_tables.is_empty());
+        assert_eq!(diff.removed_tables, vec!["legacy_sessions".to_string()]);
+        assert_eq!(diff.changed_tables.len(), 1);
+
+        let users_diff = &diff.changed_tables[0];
+        assert_eq!(users_diff.name, "users");
+        assert_eq!(users_diff.added_columns.len(), 1);
+        assert_eq!(users_diff.added_columns[0].name, "age");
+        assert!(users_diff.removed_columns.is_empty());
+        assert!(users_diff.changed_columns.is_empty());
+    }
+
+    #[test]
+    fn test_diff_reports_changed_column_type() {
+        let current = export_schema(vec![users_table()]);
+
+        let mut users = Table::new("users");
+        users.add_column(Column::new("id", ColumnType::BigInteger).primary_key().auto_increment());
+        users.add_column(Column::new("email", ColumnType::Text));
+        let desired = export_schema(vec![users]);
+
+        let diff = current.diff(&desired);
+        let change = &diff.changed_tables[0].changed_columns[0];
+        assert_eq!(change.name, "email");
+        assert_eq!(change.from.data_type, "varchar(255)");
+        assert_eq!(change.to.data_type, "text");
+        assert!(current.diff(&current).is_empty());
+    }
+}
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use crate::schema::{Column, ColumnType, Table, ForeignKey};
+use crate::schema::{CheckConstraint, Column, ColumnType, ForeignKey, ForeignKeyAction, Table, UniqueConstraint};
 
 /// Serializable schema representation for SDK generation
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -7,14 +7,26 @@ pub struct SchemaExport {
     pub tables: Vec<TableSchema>,
 }
 
+impl SchemaExport {
+    /// Rebuild the tables this schema was exported from, e.g. to generate DDL
+    /// from a stored schema
+    pub fn to_tables(&self) -> Vec<Table> {
+        self.tables.iter().map(TableSchema::to_table).collect()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TableSchema {
     pub name: String,
     pub columns: Vec<ColumnSchema>,
     pub foreign_keys: Vec<ForeignKeySchema>,
+    #[serde(default)]
+    pub unique_constraints: Vec<UniqueConstraint>,
+    #[serde(default)]
+    pub checks: Vec<CheckConstraint>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ColumnSchema {
     pub name: String,
     pub data_type: String,
@@ -24,13 +36,23 @@ pub struct ColumnSchema {
     pub unique: bool,
     pub auto_increment: bool,
     pub default_value: Option<String>,
+    #[serde(default)]
+    pub check: Option<String>,
+    #[serde(default)]
+    pub comment: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ForeignKeySchema {
+    #[serde(default)]
+    pub name: Option<String>,
     pub column: String,
     pub references_table: String,
     pub references_column: String,
+    #[serde(default)]
+    pub on_delete: Option<String>,
+    #[serde(default)]
+    pub on_update: Option<String>,
 }
 
 impl TableSchema {
@@ -40,8 +62,28 @@ impl TableSchema {
             name: table.name().to_string(),
             columns: table.columns().iter().map(ColumnSchema::from_column).collect(),
             foreign_keys: table.foreign_keys().iter().map(ForeignKeySchema::from_foreign_key).collect(),
+            unique_constraints: table.unique_constraints().to_vec(),
+            checks: table.checks().to_vec(),
         }
     }
+
+    /// Rebuild a Table from this schema
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::new(&self.name);
+        for column in &self.columns {
+            table.add_column(column.to_column());
+        }
+        for fk in &self.foreign_keys {
+            table.add_foreign_key(fk.to_foreign_key());
+        }
+        for unique in &self.unique_constraints {
+            table.add_unique(&unique.name, unique.columns.clone());
+        }
+        for check in &self.checks {
+            table.add_check(&check.name, &check.expression);
+        }
+        table
+    }
 }
 
 impl ColumnSchema {
@@ -59,7 +101,34 @@ impl ColumnSchema {
             unique: column.is_unique(),
             auto_increment: column.is_auto_increment(),
             default_value: column.default_value().map(|s| s.to_string()),
+            check: column.check_expression().map(|s| s.to_string()),
+            comment: column.comment_text().map(|s| s.to_string()),
+        }
+    }
+
+    /// Rebuild a Column from this schema, falling back to text for unknown types
+    pub fn to_column(&self) -> Column {
+        let column_type = self.data_type.parse().unwrap_or(ColumnType::Text);
+        let mut column = Column::new(&self.name, column_type).nullable(self.nullable);
+        if self.primary_key {
+            column = column.primary_key();
+        }
+        if self.unique {
+            column = column.unique();
+        }
+        if self.auto_increment {
+            column = column.auto_increment();
+        }
+        if let Some(default) = &self.default_value {
+            column = column.default(default.clone());
+        }
+        if let Some(check) = &self.check {
+            column = column.check(check.clone());
+        }
+        if let Some(comment) = &self.comment {
+            column = column.comment(comment.clone());
         }
+        column
     }
 }
 
@@ -67,16 +136,45 @@ impl ForeignKeySchema {
     /// Convert a ForeignKey to a serializable ForeignKeySchema
     pub fn from_foreign_key(fk: &ForeignKey) -> Self {
         Self {
+            name: fk.name.clone(),
             column: fk.column.clone(),
             references_table: fk.references_table.clone(),
             references_column: fk.references_column.clone(),
+            on_delete: fk.on_delete.map(|action| action.to_sql().to_string()),
+            on_update: fk.on_update.map(|action| action.to_sql().to_string()),
         }
     }
+
+    /// Rebuild a ForeignKey from this schema, dropping unrecognised actions
+    pub fn to_foreign_key(&self) -> ForeignKey {
+        ForeignKey {
+            name: self.name.clone(),
+            column: self.column.clone(),
+            references_table: self.references_table.clone(),
+            references_column: self.references_column.clone(),
+            on_delete: self.on_delete.as_deref().and_then(foreign_key_action_from_string),
+            on_update: self.on_update.as_deref().and_then(foreign_key_action_from_string),
+        }
+    }
+}
+
+/// Parse an action rendered by `ForeignKeyAction::to_sql`
+fn foreign_key_action_from_string(s: &str) -> Option<ForeignKeyAction> {
+    match s.trim().to_uppercase().as_str() {
+        "CASCADE" => Some(ForeignKeyAction::Cascade),
+        "SET NULL" => Some(ForeignKeyAction::SetNull),
+        "RESTRICT" => Some(ForeignKeyAction::Restrict),
+        "NO ACTION" => Some(ForeignKeyAction::NoAction),
+        "SET DEFAULT" => Some(ForeignKeyAction::SetDefault),
+        _ => None,
+    }
 }
 
 /// Convert ColumnType to a string representation
 fn column_type_to_string(col_type: &ColumnType) -> String {
     match col_type {
+        ColumnType::TinyInt => "tinyint".to_string(),
+        ColumnType::SmallInt => "smallint".to_string(),
         ColumnType::Integer => "integer".to_string(),
         ColumnType::BigInteger => "bigint".to_string(),
         ColumnType::Text => "text".to_string(),
@@ -91,12 +189,19 @@ fn column_type_to_string(col_type: &ColumnType) -> String {
         ColumnType::Json => "json".to_string(),
         ColumnType::Uuid => "uuid".to_string(),
         ColumnType::Binary => "binary".to_string(),
+        ColumnType::Array(element) => format!("{}[]", column_type_to_string(element)),
     }
 }
 
 /// Convert ColumnType to TypeScript type
 fn column_type_to_typescript(col_type: &ColumnType, nullable: bool) -> String {
+    let element_type;
     let base_type = match col_type {
+        ColumnType::Array(element) => {
+            element_type = format!("{}[]", column_type_to_typescript(element, false));
+            &element_type
+        }
+        ColumnType::TinyInt | ColumnType::SmallInt => "number",
         ColumnType::Integer | ColumnType::BigInteger => "number",
         ColumnType::Float | ColumnType::Double => "number",
         ColumnType::Decimal { .. } => "number",
@@ -140,6 +245,41 @@ mod tests {
         assert_eq!(column_type_to_typescript(&ColumnType::Text, false), "string");
         assert_eq!(column_type_to_typescript(&ColumnType::Boolean, false), "boolean");
         assert_eq!(column_type_to_typescript(&ColumnType::Uuid, false), "string");
+        let ints = ColumnType::Array(Box::new(ColumnType::Integer));
+        assert_eq!(column_type_to_typescript(&ints, false), "number[]");
+        assert_eq!(column_type_to_typescript(&ints, true), "number[] | null");
+    }
+
+    #[test]
+    fn test_column_type_string_round_trip() {
+        let types = vec![
+            ColumnType::TinyInt,
+            ColumnType::SmallInt,
+            ColumnType::Integer,
+            ColumnType::BigInteger,
+            ColumnType::Text,
+            ColumnType::Varchar(255),
+            ColumnType::Boolean,
+            ColumnType::Float,
+            ColumnType::Double,
+            ColumnType::Decimal { precision: 10, scale: 2 },
+            ColumnType::Date,
+            ColumnType::DateTime,
+            ColumnType::Timestamp,
+            ColumnType::Json,
+            ColumnType::Uuid,
+            ColumnType::Binary,
+            ColumnType::Array(Box::new(ColumnType::Varchar(32))),
+        ];
+        for column_type in types {
+            let parsed: ColumnType = column_type_to_string(&column_type).parse().unwrap();
+            assert_eq!(parsed, column_type);
+        }
+
+        assert_eq!("VARCHAR( 64 )".parse::<ColumnType>().unwrap(), ColumnType::Varchar(64));
+        assert!("geometry".parse::<ColumnType>().is_err());
+        assert!("decimal(10)".parse::<ColumnType>().is_err());
+        assert!("varchar(abc)".parse::<ColumnType>().is_err());
     }
 
     #[test]
@@ -173,4 +313,36 @@ mod tests {
         assert_eq!(age_col.typescript_type, "number | null");
         assert!(age_col.nullable);
     }
+
+    #[test]
+    fn test_json_round_trip_regenerates_ddl() {
+        use crate::query::builder::Dialect;
+
+        let mut table = Table::new("posts");
+        table.add_column(Column::new("id", ColumnType::BigInteger).primary_key().auto_increment());
+        table.add_column(Column::new("author_id", ColumnType::BigInteger));
+        table.add_column(Column::new("slug", ColumnType::Varchar(120)).unique());
+        table.add_column(Column::new("price", ColumnType::Decimal { precision: 10, scale: 2 }).check("price >= 0"));
+        table.add_column(Column::new("tags", ColumnType::Array(Box::new(ColumnType::Text))).nullable(true));
+        table.add_column(Column::new("published", ColumnType::Boolean).default("0"));
+        table.add_foreign_key(ForeignKey {
+            name: Some("fk_posts_author_id".to_string()),
+            column: "author_id".to_string(),
+            references_table: "users".to_string(),
+            references_column: "id".to_string(),
+            on_delete: Some(ForeignKeyAction::Cascade),
+            on_update: Some(ForeignKeyAction::SetNull),
+        });
+        table.add_unique("uq_posts_author_slug", vec!["author_id".to_string(), "slug".to_string()]);
+        table.add_check("published_flag", "published IN (0, 1)");
+
+        let json = export_schema_json(vec![table.clone()]).unwrap();
+        let restored: SchemaExport = serde_json::from_str(&json).unwrap();
+        let tables = restored.to_tables();
+
+        assert_eq!(tables.len(), 1);
+        for dialect in [Dialect::SQLite, Dialect::MySQL, Dialect::Postgres] {
+            assert_eq!(tables[0].to_create_sql(dialect), table.to_create_sql(dialect));
+        }
+    }
 }
\ No newline at end of file
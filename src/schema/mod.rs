@@ -1,7 +1,9 @@
 pub mod column;
 pub mod table;
 pub mod introspect;
+pub mod diff;
 
 pub use column::{Column, ColumnType};
-pub use table::{Table, Index, ForeignKey, ForeignKeyAction};
-pub use introspect::{SchemaExport, TableSchema, ColumnSchema, ForeignKeySchema, export_schema, export_schema_json};
\ No newline at end of file
+pub use table::{Table, Index, ForeignKey, ForeignKeyAction, CheckConstraint, UniqueConstraint};
+pub use introspect::{SchemaExport, TableSchema, ColumnSchema, ForeignKeySchema, export_schema, export_schema_json};
+pub use diff::{SchemaDiff, TableDiff, ColumnChange};
\ No newline at end of file
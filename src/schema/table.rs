@@ -1,4 +1,5 @@
 use crate::schema::Column;
+use serde::{Deserialize, Serialize};
 use crate::query::builder::Dialect;
 
 /// Represents a database table
@@ -9,6 +10,8 @@ pub struct Table {
     primary_key: Option<String>,
     indexes: Vec<Index>,
     foreign_keys: Vec<ForeignKey>,
+    checks: Vec<CheckConstraint>,
+    unique_constraints: Vec<UniqueConstraint>,
 }
 
 #[derive(Debug, Clone)]
@@ -20,6 +23,8 @@ pub struct Index {
 
 #[derive(Debug, Clone)]
 pub struct ForeignKey {
+    /// Constraint name, rendered as `CONSTRAINT name FOREIGN KEY ...` when set
+    pub name: Option<String>,
     pub column: String,
     pub references_table: String,
     pub references_column: String,
@@ -27,12 +32,27 @@ pub struct ForeignKey {
     pub on_update: Option<ForeignKeyAction>,
 }
 
+/// A named table-level `CHECK (expression)` constraint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckConstraint {
+    pub name: String,
+    pub expression: String,
+}
+
+/// A named `UNIQUE (a, b, ...)` table constraint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UniqueConstraint {
+    pub name: String,
+    pub columns: Vec<String>,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum ForeignKeyAction {
     Cascade,
     SetNull,
     Restrict,
     NoAction,
+    SetDefault,
 }
 
 impl ForeignKeyAction {
@@ -42,6 +62,7 @@ impl ForeignKeyAction {
             ForeignKeyAction::SetNull => "SET NULL",
             ForeignKeyAction::Restrict => "RESTRICT",
             ForeignKeyAction::NoAction => "NO ACTION",
+            ForeignKeyAction::SetDefault => "SET DEFAULT",
         }
     }
 }
@@ -54,6 +75,8 @@ impl Table {
             primary_key: None,
             indexes: Vec::new(),
             foreign_keys: Vec::new(),
+            checks: Vec::new(),
+            unique_constraints: Vec::new(),
         }
     }
 
@@ -70,6 +93,8 @@ impl Table {
         &self.columns
     }
 
+    /// Declare a table-level `PRIMARY KEY (column)`, ignored if a column is already declared
+    /// `primary_key()`
     pub fn set_primary_key(&mut self, column: impl Into<String>) -> &mut Self {
         self.primary_key = Some(column.into());
         self
@@ -93,6 +118,30 @@ impl Table {
         self
     }
 
+    /// Add a foreign key and return it for further configuration
+    pub(crate) fn push_foreign_key(&mut self, fk: ForeignKey) -> &mut ForeignKey {
+        self.foreign_keys.push(fk);
+        self.foreign_keys.last_mut().expect("foreign key was just pushed")
+    }
+
+    /// Add a named `CHECK (expression)` table constraint
+    pub fn add_check(&mut self, name: impl Into<String>, expression: impl Into<String>) -> &mut Self {
+        self.checks.push(CheckConstraint {
+            name: name.into(),
+            expression: expression.into(),
+        });
+        self
+    }
+
+    /// Add a named `UNIQUE` constraint spanning one or more columns
+    pub fn add_unique(&mut self, name: impl Into<String>, columns: Vec<String>) -> &mut Self {
+        self.unique_constraints.push(UniqueConstraint {
+            name: name.into(),
+            columns,
+        });
+        self
+    }
+
     pub fn indexes(&self) -> &[Index] {
         &self.indexes
     }
@@ -101,6 +150,14 @@ impl Table {
         &self.foreign_keys
     }
 
+    pub fn checks(&self) -> &[CheckConstraint] {
+        &self.checks
+    }
+
+    pub fn unique_constraints(&self) -> &[UniqueConstraint] {
+        &self.unique_constraints
+    }
+
     /// Generate CREATE TABLE SQL
     pub fn to_create_sql(&self, dialect: Dialect) -> String {
         let mut sql = format!("CREATE TABLE {} (\n", self.name);
@@ -112,9 +169,16 @@ impl Table {
         
         sql.push_str(&column_defs.join(",\n"));
         
+        if let Some(primary_key) = self.table_primary_key() {
+            sql.push_str(&format!(",\n  PRIMARY KEY ({})", primary_key));
+        }
+        
         // Add foreign keys
         for fk in &self.foreign_keys {
             sql.push_str(",\n  ");
+            if let Some(name) = &fk.name {
+                sql.push_str(&format!("CONSTRAINT {} ", name));
+            }
             sql.push_str(&format!(
                 "FOREIGN KEY ({}) REFERENCES {}({})",
                 fk.column, fk.references_table, fk.references_column
@@ -129,10 +193,43 @@ impl Table {
             }
         }
         
+        for unique in &self.unique_constraints {
+            // A primary key is already unique; repeating it adds a redundant constraint
+            if self.is_primary_key_columns(&unique.columns) {
+                continue;
+            }
+            sql.push_str(",\n  ");
+            sql.push_str(&format!("CONSTRAINT {} UNIQUE ({})", unique.name, unique.columns.join(", ")));
+        }
+        
+        for check in &self.checks {
+            sql.push_str(",\n  ");
+            sql.push_str(&format!("CONSTRAINT {} CHECK ({})", check.name, check.expression));
+        }
+        
         sql.push_str("\n)");
         sql
     }
 
+    /// The `set_primary_key` column, when it's emitted as a table-level constraint
+    ///
+    /// A column-level PRIMARY KEY takes precedence; the table-level key is then ignored.
+    fn table_primary_key(&self) -> Option<&str> {
+        self.primary_key
+            .as_deref()
+            .filter(|_| !self.columns.iter().any(|c| c.is_primary_key()))
+    }
+
+    fn is_primary_key_columns(&self, columns: &[String]) -> bool {
+        match columns {
+            [column] => {
+                self.table_primary_key() == Some(column.as_str())
+                    || self.columns.iter().any(|c| c.name() == column && c.is_primary_key())
+            }
+            _ => false,
+        }
+    }
+
     /// Generate DROP TABLE SQL
     pub fn to_drop_sql(&self) -> String {
         format!("DROP TABLE IF EXISTS {}", self.name)
@@ -1,4 +1,6 @@
+use crate::backend::map_pool_error;
 use crate::error::Result;
+use crate::model::FromRow;
 use crate::query::QueryValue;
 use sqlx::{MySqlPool, SqlitePool};
 
@@ -11,51 +13,103 @@ pub enum TransactionInner {
 /// Represents a database transaction
 pub struct Transaction {
     inner: Option<TransactionInner>,
+    read_only: bool,
+    /// The runtime the transaction began on, re-entered to release it from `Drop`
+    runtime: tokio::runtime::Handle,
 }
 
 impl Transaction {
     /// Create a new SQLite transaction
     pub(crate) async fn new_sqlite(pool: &SqlitePool) -> Result<Self> {
-        let tx = pool.begin().await?;
+        let tx = pool.begin().await.map_err(map_pool_error(pool))?;
         Ok(Self {
             inner: Some(TransactionInner::SQLite(tx)),
+            read_only: false,
+            runtime: tokio::runtime::Handle::current(),
+        })
+    }
+
+    /// Create a read-only SQLite transaction
+    ///
+    /// `PRAGMA query_only` makes SQLite reject writes on every path, including
+    /// `INSERT ... RETURNING` sent through the fetch methods. The pragma belongs to the
+    /// connection, so it's cleared again on commit or rollback, and the pool's release
+    /// hook clears it for transactions that are dropped instead.
+    pub(crate) async fn new_sqlite_read_only(pool: &SqlitePool) -> Result<Self> {
+        let mut tx = pool.begin().await.map_err(map_pool_error(pool))?;
+        sqlx::query("PRAGMA query_only = ON").execute(&mut *tx).await?;
+        Ok(Self {
+            inner: Some(TransactionInner::SQLite(tx)),
+            read_only: true,
+            runtime: tokio::runtime::Handle::current(),
         })
     }
 
     /// Create a new MySQL transaction
     pub(crate) async fn new_mysql(pool: &MySqlPool) -> Result<Self> {
-        let tx = pool.begin().await?;
+        let tx = pool.begin().await.map_err(map_pool_error(pool))?;
         Ok(Self {
             inner: Some(TransactionInner::MySQL(tx)),
+            read_only: false,
+            runtime: tokio::runtime::Handle::current(),
         })
     }
 
-    /// Commit the transaction
-    pub async fn commit(mut self) -> Result<()> {
-        if let Some(inner) = self.inner.take() {
-            match inner {
-                TransactionInner::SQLite(tx) => {
-                    tx.commit().await?;
-                }
-                TransactionInner::MySQL(tx) => {
-                    tx.commit().await?;
+    /// Create a MySQL transaction the server itself enforces as read-only
+    pub(crate) async fn new_mysql_read_only(pool: &MySqlPool) -> Result<Self> {
+        let tx = pool.begin_with("START TRANSACTION READ ONLY").await.map_err(map_pool_error(pool))?;
+        Ok(Self {
+            inner: Some(TransactionInner::MySQL(tx)),
+            read_only: true,
+            runtime: tokio::runtime::Handle::current(),
+        })
+    }
+
+    /// Whether this transaction rejects writes
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    fn ensure_writable(&self) -> Result<()> {
+        if self.read_only {
+            return Err(crate::error::Error::TransactionError(
+                "Cannot write in a read-only transaction".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Whether the transaction can still be used (it has not been committed or rolled back)
+    pub fn is_open(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    /// Commit the transaction; fails with `Error::TransactionClosed` if already finished
+    pub async fn commit(&mut self) -> Result<()> {
+        match self.inner.take() {
+            Some(TransactionInner::SQLite(mut tx)) => {
+                if self.read_only {
+                    clear_query_only(&mut tx).await?;
                 }
+                tx.commit().await?
             }
+            Some(TransactionInner::MySQL(tx)) => tx.commit().await?,
+            None => return Err(crate::error::Error::TransactionClosed),
         }
         Ok(())
     }
 
-    /// Rollback the transaction
-    pub async fn rollback(mut self) -> Result<()> {
-        if let Some(inner) = self.inner.take() {
-            match inner {
-                TransactionInner::SQLite(tx) => {
-                    tx.rollback().await?;
-                }
-                TransactionInner::MySQL(tx) => {
-                    tx.rollback().await?;
+    /// Rollback the transaction; fails with `Error::TransactionClosed` if already finished
+    pub async fn rollback(&mut self) -> Result<()> {
+        match self.inner.take() {
+            Some(TransactionInner::SQLite(mut tx)) => {
+                if self.read_only {
+                    clear_query_only(&mut tx).await?;
                 }
+                tx.rollback().await?
             }
+            Some(TransactionInner::MySQL(tx)) => tx.rollback().await?,
+            None => return Err(crate::error::Error::TransactionClosed),
         }
         Ok(())
     }
@@ -63,6 +117,7 @@ impl Transaction {
     /// Execute raw SQL within the transaction (deprecated - use execute_params)
     #[deprecated(note = "Use execute_params for SQL injection protection")]
     pub async fn execute(&mut self, sql: &str) -> Result<u64> {
+        self.ensure_writable()?;
         if let Some(inner) = &mut self.inner {
             let rows_affected = match inner {
                 TransactionInner::SQLite(tx) => {
@@ -76,52 +131,52 @@ impl Transaction {
             };
             Ok(rows_affected)
         } else {
-            Err(crate::error::Error::QueryError(
-                "Transaction already completed".to_string(),
-            ))
+            Err(crate::error::Error::TransactionClosed)
         }
     }
 
     /// Execute SQL with parameters within the transaction (safe from SQL injection)
     pub async fn execute_params(&mut self, sql: &str, params: &[QueryValue]) -> Result<u64> {
+        self.ensure_writable()?;
         if let Some(inner) = &mut self.inner {
             let rows_affected = match inner {
                 TransactionInner::SQLite(tx) => {
-                    let mut query = sqlx::query(sql);
-                    for param in params {
-                        query = match param {
-                            QueryValue::Null => query.bind(Option::<i64>::None),
-                            QueryValue::Bool(v) => query.bind(*v),
-                            QueryValue::I32(v) => query.bind(*v),
-                            QueryValue::I64(v) => query.bind(*v),
-                            QueryValue::F64(v) => query.bind(*v),
-                            QueryValue::String(v) => query.bind(v.as_str()),
-                        };
-                    }
+                    let query = crate::utils::bind_sqlite_params(sqlx::query(sql), params);
                     let result = query.execute(&mut **tx).await?;
                     result.rows_affected()
                 }
                 TransactionInner::MySQL(tx) => {
-                    let mut query = sqlx::query(sql);
-                    for param in params {
-                        query = match param {
-                            QueryValue::Null => query.bind(Option::<i64>::None),
-                            QueryValue::Bool(v) => query.bind(*v),
-                            QueryValue::I32(v) => query.bind(*v),
-                            QueryValue::I64(v) => query.bind(*v),
-                            QueryValue::F64(v) => query.bind(*v),
-                            QueryValue::String(v) => query.bind(v.as_str()),
-                        };
-                    }
+                    let query = crate::utils::bind_mysql_params(sqlx::query(sql), params);
                     let result = query.execute(&mut **tx).await?;
                     result.rows_affected()
                 }
             };
             Ok(rows_affected)
         } else {
-            Err(crate::error::Error::QueryError(
-                "Transaction already completed".to_string(),
-            ))
+            Err(crate::error::Error::TransactionClosed)
+        }
+    }
+
+    /// Execute a script of multiple SQL statements (no parameters) within the transaction,
+    /// returning total rows affected
+    ///
+    /// A failing statement stops the script; roll back to discard the ones before it.
+    pub async fn execute_batch(&mut self, sql: &str) -> Result<u64> {
+        self.ensure_writable()?;
+        if let Some(inner) = &mut self.inner {
+            let rows_affected = match inner {
+                TransactionInner::SQLite(tx) => {
+                    let result = sqlx::raw_sql(sql).execute(&mut **tx).await?;
+                    result.rows_affected()
+                }
+                TransactionInner::MySQL(tx) => {
+                    let result = sqlx::raw_sql(sql).execute(&mut **tx).await?;
+                    result.rows_affected()
+                }
+            };
+            Ok(rows_affected)
+        } else {
+            Err(crate::error::Error::TransactionClosed)
         }
     }
 
@@ -141,9 +196,7 @@ impl Transaction {
             };
             Ok(results)
         } else {
-            Err(crate::error::Error::QueryError(
-                "Transaction already completed".to_string(),
-            ))
+            Err(crate::error::Error::TransactionClosed)
         }
     }
 
@@ -152,41 +205,19 @@ impl Transaction {
         if let Some(inner) = &mut self.inner {
             let results = match inner {
                 TransactionInner::SQLite(tx) => {
-                    let mut query = sqlx::query(sql);
-                    for param in params {
-                        query = match param {
-                            QueryValue::Null => query.bind(Option::<i64>::None),
-                            QueryValue::Bool(v) => query.bind(*v),
-                            QueryValue::I32(v) => query.bind(*v),
-                            QueryValue::I64(v) => query.bind(*v),
-                            QueryValue::F64(v) => query.bind(*v),
-                            QueryValue::String(v) => query.bind(v.as_str()),
-                        };
-                    }
+                    let query = crate::utils::bind_sqlite_params(sqlx::query(sql), params);
                     let rows = query.fetch_all(&mut **tx).await?;
                     rows.iter().map(crate::utils::sqlite_row_to_json).collect()
                 }
                 TransactionInner::MySQL(tx) => {
-                    let mut query = sqlx::query(sql);
-                    for param in params {
-                        query = match param {
-                            QueryValue::Null => query.bind(Option::<i64>::None),
-                            QueryValue::Bool(v) => query.bind(*v),
-                            QueryValue::I32(v) => query.bind(*v),
-                            QueryValue::I64(v) => query.bind(*v),
-                            QueryValue::F64(v) => query.bind(*v),
-                            QueryValue::String(v) => query.bind(v.as_str()),
-                        };
-                    }
+                    let query = crate::utils::bind_mysql_params(sqlx::query(sql), params);
                     let rows = query.fetch_all(&mut **tx).await?;
                     rows.iter().map(crate::utils::mysql_row_to_json).collect()
                 }
             };
             Ok(results)
         } else {
-            Err(crate::error::Error::QueryError(
-                "Transaction already completed".to_string(),
-            ))
+            Err(crate::error::Error::TransactionClosed)
         }
     }
 
@@ -206,9 +237,7 @@ impl Transaction {
             };
             Ok(result)
         } else {
-            Err(crate::error::Error::QueryError(
-                "Transaction already completed".to_string(),
-            ))
+            Err(crate::error::Error::TransactionClosed)
         }
     }
 
@@ -217,48 +246,53 @@ impl Transaction {
         if let Some(inner) = &mut self.inner {
             let result = match inner {
                 TransactionInner::SQLite(tx) => {
-                    let mut query = sqlx::query(sql);
-                    for param in params {
-                        query = match param {
-                            QueryValue::Null => query.bind(Option::<i64>::None),
-                            QueryValue::Bool(v) => query.bind(*v),
-                            QueryValue::I32(v) => query.bind(*v),
-                            QueryValue::I64(v) => query.bind(*v),
-                            QueryValue::F64(v) => query.bind(*v),
-                            QueryValue::String(v) => query.bind(v.as_str()),
-                        };
-                    }
+                    let query = crate::utils::bind_sqlite_params(sqlx::query(sql), params);
                     let row_opt = query.fetch_optional(&mut **tx).await?;
                     row_opt.as_ref().map(crate::utils::sqlite_row_to_json)
                 }
                 TransactionInner::MySQL(tx) => {
-                    let mut query = sqlx::query(sql);
-                    for param in params {
-                        query = match param {
-                            QueryValue::Null => query.bind(Option::<i64>::None),
-                            QueryValue::Bool(v) => query.bind(*v),
-                            QueryValue::I32(v) => query.bind(*v),
-                            QueryValue::I64(v) => query.bind(*v),
-                            QueryValue::F64(v) => query.bind(*v),
-                            QueryValue::String(v) => query.bind(v.as_str()),
-                        };
-                    }
+                    let query = crate::utils::bind_mysql_params(sqlx::query(sql), params);
                     let row_opt = query.fetch_optional(&mut **tx).await?;
                     row_opt.as_ref().map(crate::utils::mysql_row_to_json)
                 }
             };
             Ok(result)
         } else {
-            Err(crate::error::Error::QueryError(
-                "Transaction already completed".to_string(),
-            ))
+            Err(crate::error::Error::TransactionClosed)
+        }
+    }
+
+    /// Fetch all rows and hydrate them into models
+    pub async fn fetch_all_as<T: FromRow>(&mut self, sql: &str, params: &[QueryValue]) -> Result<Vec<T>> {
+        self.fetch_all_params(sql, params)
+            .await?
+            .iter()
+            .map(T::from_json)
+            .collect()
+    }
+
+    /// Fetch one row and hydrate it into a model
+    pub async fn fetch_one_as<T: FromRow>(&mut self, sql: &str, params: &[QueryValue]) -> Result<Option<T>> {
+        match self.fetch_one_params(sql, params).await? {
+            Some(json) => Ok(Some(T::from_json(&json)?)),
+            None => Ok(None),
         }
     }
 }
 
 impl Drop for Transaction {
     fn drop(&mut self) {
-        // Auto-rollback on drop if transaction wasn't committed or rolled back
-        // The sqlx transaction will handle this automatically
+        // sqlx rolls back an unfinished transaction and hands its connection back to the
+        // pool on a spawned task, so re-enter the runtime it began on when dropped outside
+        // one. If that runtime has shut down the connection is discarded instead.
+        let _guard = tokio::runtime::Handle::try_current()
+            .is_err()
+            .then(|| self.runtime.enter());
+        self.inner.take();
     }
-}
\ No newline at end of file
+}
+
+async fn clear_query_only(tx: &mut sqlx::Transaction<'static, sqlx::Sqlite>) -> Result<()> {
+    sqlx::query("PRAGMA query_only = OFF").execute(&mut **tx).await?;
+    Ok(())
+}
@@ -1,4 +1,46 @@
-use sqlx::{Column, Row};
+use crate::query::QueryValue;
+use sqlx::{Column, Row, ValueRef};
+
+type SqliteQuery<'q> = sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>>;
+type MySqlQuery<'q> = sqlx::query::Query<'q, sqlx::MySql, sqlx::mysql::MySqlArguments>;
+
+/// Bind parameters to a SQLite query
+pub fn bind_sqlite_params<'q>(mut query: SqliteQuery<'q>, params: &'q [QueryValue]) -> SqliteQuery<'q> {
+    for param in params {
+        query = match param {
+            // Bound with sqlite3_bind_null: the NULL carries no type, whatever the Rust type here
+            QueryValue::Null => query.bind(Option::<i64>::None),
+            // SQLite has no boolean type; bind 0/1 so writes match what reads decode
+            QueryValue::Bool(v) => query.bind(*v as i64),
+            QueryValue::I8(v) => query.bind(*v),
+            QueryValue::I16(v) => query.bind(*v),
+            QueryValue::I32(v) => query.bind(*v),
+            QueryValue::I64(v) => query.bind(*v),
+            QueryValue::F64(v) => query.bind(*v),
+            QueryValue::String(v) => query.bind(v.as_str()),
+            QueryValue::Array(_) => query.bind(param.to_json().to_string()),
+        };
+    }
+    query
+}
+
+/// Bind parameters to a MySQL query
+pub fn bind_mysql_params<'q>(mut query: MySqlQuery<'q>, params: &'q [QueryValue]) -> MySqlQuery<'q> {
+    for param in params {
+        query = match param {
+            QueryValue::Null => query.bind(Option::<i64>::None),
+            QueryValue::Bool(v) => query.bind(*v),
+            QueryValue::I8(v) => query.bind(*v),
+            QueryValue::I16(v) => query.bind(*v),
+            QueryValue::I32(v) => query.bind(*v),
+            QueryValue::I64(v) => query.bind(*v),
+            QueryValue::F64(v) => query.bind(*v),
+            QueryValue::String(v) => query.bind(v.as_str()),
+            QueryValue::Array(_) => query.bind(param.to_json().to_string()),
+        };
+    }
+    query
+}
 
 /// Convert a SQLite row to JSON
 pub fn sqlite_row_to_json(row: &sqlx::sqlite::SqliteRow) -> serde_json::Value {
@@ -6,7 +48,10 @@ pub fn sqlite_row_to_json(row: &sqlx::sqlite::SqliteRow) -> serde_json::Value {
     for (i, column) in row.columns().iter().enumerate() {
         let column_name = column.name();
         
-        let value = if let Ok(v) = row.try_get::<i64, _>(i) {
+        // SQLite decodes NULL as a default value for non-Option types, so check it first
+        let value = if row.try_get_raw(i).is_ok_and(|raw| raw.is_null()) {
+            serde_json::Value::Null
+        } else if let Ok(v) = row.try_get::<i64, _>(i) {
             serde_json::json!(v)
         } else if let Ok(v) = row.try_get::<f64, _>(i) {
             serde_json::json!(v)
@@ -52,6 +97,55 @@ pub fn mysql_row_to_json(row: &sqlx::mysql::MySqlRow) -> serde_json::Value {
     serde_json::Value::Object(obj)
 }
 
+/// Check that a name is a plain or quoted identifier, optionally dotted (`table.column`)
+///
+/// A `*` is allowed as the last segment (`users.*`).
+pub fn validate_identifier(name: &str) -> crate::error::Result<()> {
+    let segments: Vec<&str> = name.split('.').collect();
+    let valid = segments.iter().enumerate().all(|(i, segment)| {
+        is_identifier_segment(segment) || (*segment == "*" && i == segments.len() - 1)
+    });
+
+    if valid {
+        Ok(())
+    } else {
+        Err(crate::error::Error::QueryError(format!("Invalid identifier: {}", name)))
+    }
+}
+
+/// Check a table reference: an identifier with an optional alias (`users u`, `users AS u`)
+pub fn validate_table_reference(reference: &str) -> crate::error::Result<()> {
+    let parts: Vec<&str> = reference.split_whitespace().collect();
+    let valid = match parts.as_slice() {
+        [table] => validate_identifier(table).is_ok(),
+        [table, alias] => validate_identifier(table).is_ok() && is_identifier_segment(alias),
+        [table, keyword, alias] => {
+            validate_identifier(table).is_ok()
+                && keyword.eq_ignore_ascii_case("as")
+                && is_identifier_segment(alias)
+        }
+        _ => false,
+    };
+
+    if valid {
+        Ok(())
+    } else {
+        Err(crate::error::Error::QueryError(format!("Invalid table reference: {}", reference)))
+    }
+}
+
+fn is_identifier_segment(segment: &str) -> bool {
+    for quote in ['"', '`'] {
+        if let Some(inner) = segment.strip_prefix(quote).and_then(|s| s.strip_suffix(quote)) {
+            return !inner.is_empty() && !inner.contains(quote);
+        }
+    }
+
+    let mut chars = segment.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
 /// Simple base64 encoding without external dependency
 fn base64_encode(bytes: &[u8]) -> String {
     const CHARSET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
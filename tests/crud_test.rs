@@ -1,4 +1,4 @@
-use orm::{prelude::*, query::QueryValue};
+use orm::{prelude::*, query::QueryValue, schema::ColumnType};
 use std::collections::HashMap;
 
 /// Test User model
@@ -37,6 +37,15 @@ impl Model for User {
     fn columns() -> Vec<&'static str> {
         vec!["name", "email", "age"]
     }
+
+    fn column_types() -> Vec<(&'static str, ColumnType)> {
+        vec![
+            ("id", ColumnType::BigInteger),
+            ("name", ColumnType::Text),
+            ("email", ColumnType::Varchar(255)),
+            ("age", ColumnType::Integer),
+        ]
+    }
 }
 
 impl FromRow for User {
@@ -231,3 +240,1127 @@ async fn test_query_builder() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_row_ext_matches_manual_from_row() -> Result<()> {
+    let mut row = orm::model::Row::new();
+    row.insert("id".to_string(), Value::I64(7));
+    row.insert("name".to_string(), Value::String("Dana".to_string()));
+    row.insert("email".to_string(), Value::String("dana@example.com".to_string()));
+    row.insert("age".to_string(), Value::I64(41));
+    row.insert("active".to_string(), Value::I64(1));
+
+    let manual = User::from_row(&row)?;
+    let typed = User {
+        id: row.get_opt_i64("id"),
+        name: row.get_string("name")?,
+        email: row.get_string("email")?,
+        age: row.get_i32("age")?,
+    };
+
+    assert_eq!(manual.id, typed.id);
+    assert_eq!(manual.name, typed.name);
+    assert_eq!(manual.email, typed.email);
+    assert_eq!(manual.age, typed.age);
+    assert!(row.get_bool("active")?);
+    assert_eq!(row.get_opt_i64("missing"), None);
+    assert!(row.get_string("missing").is_err());
+
+    // A value of the wrong type names the column and the value rather than reporting it missing
+    row.insert("flag".to_string(), Value::I64(2));
+    match row.get_bool("flag") {
+        Err(Error::SerializationError(message)) => assert_eq!(message, "flag: expected bool, got I64(2)"),
+        other => panic!("unexpected {:?}", other),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_value_numeric_coercions() {
+    let one = Value::I64(1);
+    assert_eq!(one.as_i32(), Some(1));
+    assert_eq!(one.as_bool(), Some(true));
+    assert_eq!(one.as_f64(), Some(1.0));
+    assert_eq!(Value::I64(0).as_bool(), Some(false));
+    assert_eq!(Value::I64(2).as_bool(), None);
+    assert_eq!(Value::I64(i64::MAX).as_i32(), None);
+    assert_eq!(Value::I32(5).as_i64(), Some(5));
+
+    let text = Value::String("42".to_string());
+    assert_eq!(text.as_i64(), None);
+    assert_eq!(text.as_str(), Some("42"));
+    assert_eq!(Value::Null.as_str(), None);
+}
+
+#[tokio::test]
+async fn test_explain_query_plan() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    backend.execute("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, email TEXT NOT NULL, age INTEGER NOT NULL)", &[]).await?;
+    backend.execute("CREATE INDEX idx_users_email ON users (email)", &[]).await?;
+
+    let plan = User::query(backend)
+        .where_eq("email", QueryValue::String("alice@example.com".to_string()))
+        .explain()
+        .await?;
+    assert!(!plan.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_first_or_default() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    backend.execute("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, email TEXT NOT NULL, age INTEGER NOT NULL)", &[]).await?;
+    User::create(backend, &User { id: None, name: "Alice".to_string(), email: "alice@example.com".to_string(), age: 30 }).await?;
+
+    let fallback = User { id: None, name: "Guest".to_string(), email: "guest@example.com".to_string(), age: 0 };
+
+    let missing = User::query(backend)
+        .where_eq("name", "Nobody".into())
+        .first_or(fallback.clone())
+        .await?;
+    assert_eq!(missing.name, "Guest");
+
+    let existing = User::query(backend)
+        .where_eq("name", "Alice".into())
+        .first_or(fallback)
+        .await?;
+    assert_eq!(existing.name, "Alice");
+    assert!(existing.id.is_some());
+
+    let computed = User::query(backend)
+        .where_eq("age", 99.into())
+        .first_or_else(|| User { id: None, name: "Computed".to_string(), email: String::new(), age: 99 })
+        .await?;
+    assert_eq!(computed.name, "Computed");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_query_uses_declared_column_types() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let query = User::query(db.backend());
+
+    let types: Vec<(&str, &ColumnType)> = query
+        .columns()
+        .iter()
+        .map(|c| (c.name(), c.column_type()))
+        .collect();
+    assert_eq!(
+        types,
+        vec![
+            ("id", &ColumnType::BigInteger),
+            ("name", &ColumnType::Text),
+            ("email", &ColumnType::Varchar(255)),
+            ("age", &ColumnType::Integer),
+        ]
+    );
+    assert_eq!(query.to_sql()?, "SELECT id, name, email, age FROM users");
+
+    Ok(())
+}
+
+/// Model whose rows live in a per-tenant table
+#[derive(Debug, Clone)]
+pub struct Note {
+    pub id: Option<i64>,
+    pub tenant_id: i64,
+    pub body: String,
+}
+
+impl Model for Note {
+    fn table_name() -> &'static str {
+        "notes"
+    }
+
+    fn table_name_dyn(&self) -> String {
+        format!("tenant_{}_notes", self.tenant_id)
+    }
+
+    fn primary_key_value(&self) -> Option<Value> {
+        self.id.map(Value::I64)
+    }
+
+    fn to_values(&self) -> HashMap<String, Value> {
+        let mut values = HashMap::new();
+        if let Some(id) = self.id {
+            values.insert("id".to_string(), Value::I64(id));
+        }
+        values.insert("tenant_id".to_string(), Value::I64(self.tenant_id));
+        values.insert("body".to_string(), Value::String(self.body.clone()));
+        values
+    }
+
+    fn columns() -> Vec<&'static str> {
+        vec!["tenant_id", "body"]
+    }
+}
+
+impl FromRow for Note {
+    fn from_row(row: &orm::model::Row) -> Result<Self> {
+        Ok(Note {
+            id: row.get_opt_i64("id"),
+            tenant_id: row.get_i64("tenant_id")?,
+            body: row.get_string("body")?,
+        })
+    }
+}
+
+impl ModelCrud for Note {}
+
+#[tokio::test]
+async fn test_dynamic_table_name_routes_crud() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    // Only the tenant table exists, so any use of the static name would fail
+    backend.execute("CREATE TABLE tenant_7_notes (id INTEGER PRIMARY KEY AUTOINCREMENT, tenant_id INTEGER NOT NULL, body TEXT NOT NULL)", &[]).await?;
+
+    let mut note = Note::create(backend, &Note { id: None, tenant_id: 7, body: "hello".to_string() }).await?;
+    assert!(note.id.is_some());
+
+    note.body = "updated".to_string();
+    note.update(backend).await?;
+    let stored = Note::query(backend).table("tenant_7_notes").first().await?.unwrap();
+    assert_eq!(stored.body, "updated");
+
+    note.delete(backend).await?;
+    let remaining = backend.fetch_all_params("SELECT id FROM tenant_7_notes", &[]).await?;
+    assert!(remaining.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_where_eq_ci_matches_any_case() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    backend.execute("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, email TEXT NOT NULL, age INTEGER NOT NULL)", &[]).await?;
+    User::create(backend, &User { id: None, name: "Alice".to_string(), email: "Alice@Example.com".to_string(), age: 30 }).await?;
+
+    let exact = User::query(backend)
+        .where_eq("email", "alice@example.com".into())
+        .first()
+        .await?;
+    assert!(exact.is_none());
+
+    let query = User::query(backend).where_eq_ci("email", "alice@example.com".into());
+    assert!(query.to_sql()?.ends_with("WHERE LOWER(email) = LOWER(?)"));
+    let found = query.first().await?.expect("case-insensitive match");
+    assert_eq!(found.email, "Alice@Example.com");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_where_between_is_inclusive() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    backend.execute("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, email TEXT NOT NULL, age INTEGER NOT NULL)", &[]).await?;
+    for (name, age) in [("Teen", 19), ("Low", 20), ("Mid", 25), ("High", 30), ("Senior", 31)] {
+        User::create(backend, &User { id: None, name: name.to_string(), email: format!("{}@example.com", name), age }).await?;
+    }
+
+    let query = User::query(backend)
+        .where_between("age", 20.into(), 30.into())
+        .order_by("age", OrderDirection::Asc);
+    assert!(query.to_sql()?.ends_with("WHERE age BETWEEN ? AND ? ORDER BY age ASC"));
+    let names: Vec<String> = query.get().await?.into_iter().map(|u| u.name).collect();
+    assert_eq!(names, vec!["Low", "Mid", "High"]);
+
+    let outside = User::query(backend)
+        .where_not_between("age", 20.into(), 30.into())
+        .where_eq("name", "Senior".into())
+        .get()
+        .await?;
+    assert_eq!(outside.len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_save_inserts_then_updates() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    backend.execute("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, email TEXT NOT NULL, age INTEGER NOT NULL)", &[]).await?;
+
+    let mut user = User { id: None, name: "Eve".to_string(), email: "eve@example.com".to_string(), age: 22 };
+    user.save(backend).await?;
+    let id = user.id.expect("id populated after insert");
+
+    user.age = 23;
+    user.save(backend).await?;
+    assert_eq!(user.id, Some(id));
+
+    assert_eq!(User::count(backend).await?, 1);
+    let stored = User::find(backend, Value::I64(id)).await?.expect("saved user");
+    assert_eq!(stored.age, 23);
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Post {
+    pub id: Option<i64>,
+    pub author_id: i64,
+    pub published: bool,
+    pub metadata: serde_json::Value,
+}
+
+impl Model for Post {
+    fn table_name() -> &'static str {
+        "posts"
+    }
+
+    fn timestamps() -> bool {
+        true
+    }
+
+    fn primary_key_value(&self) -> Option<Value> {
+        self.id.map(Value::I64)
+    }
+
+    fn to_values(&self) -> HashMap<String, Value> {
+        let mut values = HashMap::new();
+        if let Some(id) = self.id {
+            values.insert("id".to_string(), Value::I64(id));
+        }
+        values.insert("author_id".to_string(), Value::I64(self.author_id));
+        values.insert("published".to_string(), Value::Bool(self.published));
+        values.insert("metadata".to_string(), self.metadata.to_value());
+        values
+    }
+
+    fn columns() -> Vec<&'static str> {
+        vec!["author_id", "published", "metadata"]
+    }
+}
+
+impl FromRow for Post {
+    fn from_row(row: &orm::model::Row) -> Result<Self> {
+        Ok(Post {
+            id: row.get_opt_i64("id"),
+            author_id: row.get_i64("author_id")?,
+            published: row.get_bool("published")?,
+            metadata: row.get_as("metadata")?,
+        })
+    }
+}
+
+impl ModelCrud for Post {}
+
+/// Create the table behind `Post`, with the DB-defaulted timestamp columns it doesn't map
+async fn create_posts_table(backend: &dyn Backend) -> Result<()> {
+    backend.execute(
+        "CREATE TABLE posts (id INTEGER PRIMARY KEY AUTOINCREMENT, author_id INTEGER NOT NULL, published INTEGER NOT NULL, \
+         metadata JSON NOT NULL DEFAULT '{}', created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP, \
+         updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP)",
+        &[],
+    ).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_count_distinct_respects_filters() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    create_posts_table(backend).await?;
+    for (author_id, published) in [(1, true), (1, true), (2, true), (3, false), (3, true), (4, false)] {
+        Post::create(backend, &Post { id: None, author_id, published, ..Default::default() }).await?;
+    }
+
+    assert_eq!(Post::query(backend).count_distinct("author_id").await?, 4);
+    assert_eq!(
+        Post::query(backend)
+            .where_eq("published", QueryValue::Bool(true))
+            .count_distinct("author_id")
+            .await?,
+        3
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_delete_where_is_parameterized() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    backend.execute("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, email TEXT NOT NULL, age INTEGER NOT NULL)", &[]).await?;
+    for (name, age) in [("A", 25), ("B", 30), ("C", 35), ("D", 40), ("O'Hare", 25)] {
+        User::create(backend, &User { id: None, name: name.to_string(), email: format!("{}@example.com", age), age }).await?;
+    }
+
+    assert_eq!(User::delete_where(backend, "name", "O'Hare".into()).await?, 1);
+    assert_eq!(User::delete_where_cmp(backend, "age", ">", 30.into()).await?, 2);
+    assert!(User::delete_where_cmp(backend, "age", "> 0 OR 1 =", 1.into()).await.is_err());
+    assert_eq!(User::count(backend).await?, 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_count_where_matches_inserted() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    backend.execute("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, email TEXT NOT NULL, age INTEGER NOT NULL)", &[]).await?;
+    for (name, age) in [("A", 28), ("B", 28), ("C", 28), ("D", 41)] {
+        User::create(backend, &User { id: None, name: name.to_string(), email: format!("{}@example.com", name), age }).await?;
+    }
+
+    assert_eq!(User::count_where(backend, "age", 28.into()).await?, 3);
+    assert_eq!(User::count_where(backend, "age", 99.into()).await?, 0);
+    assert_eq!(User::query(backend).where_cmp("age", ">", 30.into()).count().await?, 1);
+    assert_eq!(User::count(backend).await?, 4);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_by_ids_only_touches_listed_rows() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    create_posts_table(backend).await?;
+    let mut ids = Vec::new();
+    for author_id in 1..=5 {
+        let post = Post::create(backend, &Post { id: None, author_id, published: false, ..Default::default() }).await?;
+        ids.push(post.id.unwrap());
+    }
+
+    let chosen = [Value::I64(ids[0]), Value::I64(ids[2]), Value::I64(ids[4])];
+    let updated = Post::update_by_ids(backend, &chosen, &[("published", QueryValue::Bool(true))]).await?;
+    assert_eq!(updated, 3);
+
+    let published: Vec<i64> = Post::query(backend)
+        .where_eq("published", QueryValue::Bool(true))
+        .order_by("id", OrderDirection::Asc)
+        .get()
+        .await?
+        .into_iter()
+        .filter_map(|p| p.id)
+        .collect();
+    assert_eq!(published, vec![ids[0], ids[2], ids[4]]);
+    assert_eq!(Post::update_by_ids(backend, &[], &[("published", QueryValue::Bool(false))]).await?, 0);
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Status {
+    Active,
+    Inactive,
+}
+
+impl FromValue for Status {
+    fn from_value(value: &Value) -> Result<Self> {
+        match value.as_str() {
+            Some("active") => Ok(Status::Active),
+            Some("inactive") => Ok(Status::Inactive),
+            _ => Err(Error::SerializationError(format!("Invalid status {:?}", value))),
+        }
+    }
+}
+
+impl ToValue for Status {
+    fn to_value(&self) -> Value {
+        match self {
+            Status::Active => "active".into(),
+            Status::Inactive => "inactive".into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Account {
+    pub id: Option<i64>,
+    pub status: Status,
+    pub closed_reason: Option<String>,
+}
+
+impl Model for Account {
+    fn table_name() -> &'static str {
+        "accounts"
+    }
+
+    fn primary_key_value(&self) -> Option<Value> {
+        self.id.map(Value::I64)
+    }
+
+    fn to_values(&self) -> HashMap<String, Value> {
+        let mut values = HashMap::new();
+        if let Some(id) = self.id {
+            values.insert("id".to_string(), Value::I64(id));
+        }
+        values.insert("status".to_string(), self.status.to_value());
+        values.insert("closed_reason".to_string(), self.closed_reason.to_value());
+        values
+    }
+
+    fn columns() -> Vec<&'static str> {
+        vec!["status", "closed_reason"]
+    }
+}
+
+impl FromRow for Account {
+    fn from_row(row: &orm::model::Row) -> Result<Self> {
+        Ok(Account {
+            id: row.get_as("id")?,
+            status: row.get_as("status")?,
+            closed_reason: row.get_as("closed_reason")?,
+        })
+    }
+}
+
+impl ModelCrud for Account {}
+
+#[tokio::test]
+async fn test_enum_field_round_trips() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    backend.execute("CREATE TABLE accounts (id INTEGER PRIMARY KEY AUTOINCREMENT, status TEXT NOT NULL, closed_reason TEXT)", &[]).await?;
+    let created = Account::create(backend, &Account { id: None, status: Status::Inactive, closed_reason: Some("moved".to_string()) }).await?;
+    Account::create(backend, &Account { id: None, status: Status::Active, closed_reason: None }).await?;
+
+    let found = Account::find(backend, Value::I64(created.id.unwrap())).await?.unwrap();
+    assert_eq!(found.status, Status::Inactive);
+    assert_eq!(found.closed_reason.as_deref(), Some("moved"));
+
+    let active = Account::query(backend).where_eq("status", Status::Active.to_value().to_query_value()).get().await?;
+    assert_eq!(active.len(), 1);
+    assert_eq!(active[0].closed_reason, None);
+
+    backend.execute("UPDATE accounts SET status = 'unknown'", &[]).await?;
+    assert!(matches!(Account::all(backend).await, Err(Error::SerializationError(_))));
+
+    Ok(())
+}
+
+/// Deliberately not `Clone`: hooks must work without copying the model
+#[derive(Debug)]
+pub struct Credential {
+    pub id: Option<i64>,
+    pub username: String,
+    pub password: String,
+    pub created_event_fired: bool,
+}
+
+impl Model for Credential {
+    fn table_name() -> &'static str {
+        "credentials"
+    }
+
+    fn primary_key_value(&self) -> Option<Value> {
+        self.id.map(Value::I64)
+    }
+
+    fn to_values(&self) -> HashMap<String, Value> {
+        let mut values = HashMap::new();
+        if let Some(id) = self.id {
+            values.insert("id".to_string(), Value::I64(id));
+        }
+        values.insert("username".to_string(), self.username.to_value());
+        values.insert("password".to_string(), self.password.to_value());
+        values
+    }
+
+    fn columns() -> Vec<&'static str> {
+        vec!["username", "password"]
+    }
+}
+
+impl FromRow for Credential {
+    fn from_row(row: &orm::model::Row) -> Result<Self> {
+        Ok(Credential {
+            id: row.get_as("id")?,
+            username: row.get_as("username")?,
+            password: row.get_as("password")?,
+            created_event_fired: false,
+        })
+    }
+}
+
+impl ModelCrud for Credential {
+    fn before_create(&self, values: &mut HashMap<String, Value>) -> Result<()> {
+        if self.password.is_empty() {
+            return Err(Error::QueryError("Password is required".to_string()));
+        }
+        let hashed = format!("hashed:{}", self.password.chars().rev().collect::<String>());
+        values.insert("password".to_string(), Value::String(hashed));
+        Ok(())
+    }
+
+    fn after_create(&mut self) -> Result<()> {
+        self.created_event_fired = true;
+        Ok(())
+    }
+
+    fn before_delete(&self) -> Result<()> {
+        if self.username == "admin" {
+            return Err(Error::QueryError("The admin account cannot be deleted".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_lifecycle_hooks() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    backend.execute("CREATE TABLE credentials (id INTEGER PRIMARY KEY AUTOINCREMENT, username TEXT NOT NULL UNIQUE, password TEXT NOT NULL)", &[]).await?;
+    let new = |username: &str, password: &str| Credential {
+        id: None,
+        username: username.to_string(),
+        password: password.to_string(),
+        created_event_fired: false,
+    };
+
+    let admin = Credential::create(backend, &new("admin", "secret")).await?;
+    assert!(admin.created_event_fired);
+    let stored = Credential::find(backend, Value::I64(admin.id.unwrap())).await?.unwrap();
+    assert_eq!(stored.password, "hashed:terces");
+
+    assert!(Credential::create(backend, &new("guest", "")).await.is_err());
+    assert!(admin.delete(backend).await.is_err());
+    assert_eq!(Credential::count(backend).await?, 1);
+
+    // Upsert runs the same hooks as create
+    let upserted = Credential::upsert(backend, &new("admin", "changed"), &["username"]).await?;
+    assert!(upserted.created_event_fired);
+    assert_eq!(upserted.id, admin.id);
+    assert_eq!(upserted.password, "hashed:degnahc");
+    assert!(Credential::upsert(backend, &new("admin", ""), &["username"]).await.is_err());
+    assert_eq!(Credential::count(backend).await?, 1);
+
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+pub struct Contact {
+    pub id: Option<i64>,
+    pub name: String,
+    pub email: String,
+}
+
+impl Model for Contact {
+    fn table_name() -> &'static str {
+        "contacts"
+    }
+
+    fn primary_key_value(&self) -> Option<Value> {
+        self.id.map(Value::I64)
+    }
+
+    fn to_values(&self) -> HashMap<String, Value> {
+        let mut values = HashMap::new();
+        if let Some(id) = self.id {
+            values.insert("id".to_string(), Value::I64(id));
+        }
+        values.insert("name".to_string(), self.name.to_value());
+        values.insert("email".to_string(), self.email.to_value());
+        values
+    }
+
+    fn columns() -> Vec<&'static str> {
+        vec!["name", "email"]
+    }
+}
+
+impl FromRow for Contact {
+    fn from_row(row: &orm::model::Row) -> Result<Self> {
+        Ok(Contact {
+            id: row.get_as("id")?,
+            name: row.get_as("name")?,
+            email: row.get_as("email")?,
+        })
+    }
+}
+
+impl Validate for Contact {
+    fn validate(&self) -> std::result::Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        if self.name.trim().is_empty() {
+            errors.push(ValidationError::new("name", "must not be empty"));
+        }
+        if !self.email.contains('@') {
+            errors.push(ValidationError::new("email", "must be a valid email address"));
+        }
+        if errors.is_empty() { Ok(()) } else { Err(errors) }
+    }
+}
+
+impl ModelCrud for Contact {
+    fn validator(&self) -> Option<&dyn Validate> {
+        Some(self)
+    }
+}
+
+#[tokio::test]
+async fn test_validation_runs_before_writes() -> Result<()> {
+    // No table exists, so reaching the database would fail with a DatabaseError
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    let invalid = Contact { id: None, name: " ".to_string(), email: "nobody".to_string() };
+    match Contact::create(backend, &invalid).await {
+        Err(Error::Validation(errors)) => {
+            let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+            assert_eq!(fields, vec!["name", "email"]);
+        }
+        other => panic!("expected validation error, got {:?}", other),
+    }
+
+    let invalid_update = Contact { id: Some(1), name: String::new(), email: "a@example.com".to_string() };
+    assert!(matches!(invalid_update.update(backend).await, Err(Error::Validation(_))));
+
+    backend.execute("CREATE TABLE contacts (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, email TEXT NOT NULL)", &[]).await?;
+    let valid = Contact { id: None, name: "Ada".to_string(), email: "ada@example.com".to_string() };
+    assert!(Contact::create(backend, &valid).await?.id.is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_chunk_processes_batches() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    create_posts_table(backend).await?;
+    for author_id in 1..=25 {
+        Post::create(backend, &Post { id: None, author_id, published: true, ..Default::default() }).await?;
+    }
+
+    let mut sizes = Vec::new();
+    let mut seen = Vec::new();
+    Post::query(backend)
+        .order_by("id", OrderDirection::Asc)
+        .chunk(10, |batch| {
+            sizes.push(batch.len());
+            seen.extend(batch.iter().map(|p| p.author_id));
+            Ok(())
+        })
+        .await?;
+
+    assert_eq!(sizes, vec![10, 10, 5]);
+    assert_eq!(seen, (1..=25).collect::<Vec<_>>());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_ignores_db_defaulted_columns() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    create_posts_table(backend).await?;
+    let created = Post::create(backend, &Post { id: None, author_id: 7, published: false, ..Default::default() }).await?;
+    assert_eq!(created.author_id, 7);
+    assert!(!created.published);
+
+    let row = backend
+        .fetch_one_params("SELECT created_at FROM posts WHERE id = ?", &[QueryValue::I64(created.id.unwrap())])
+        .await?
+        .unwrap();
+    assert!(row.get("created_at").and_then(|v| v.as_str()).is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_from_sql_hydrates_join_results() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    backend.execute("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, email TEXT NOT NULL, age INTEGER NOT NULL)", &[]).await?;
+    create_posts_table(backend).await?;
+    for (name, age) in [("Ann", 30), ("Ben", 40), ("Cat", 50)] {
+        let user = User::create(backend, &User { id: None, name: name.to_string(), email: format!("{}@example.com", name), age }).await?;
+        let published = name != "Ben";
+        Post::create(backend, &Post { id: None, author_id: user.id.unwrap(), published, ..Default::default() }).await?;
+    }
+
+    let sql = "SELECT DISTINCT u.id, u.name, u.email, u.age FROM users u \
+               JOIN posts p ON p.author_id = u.id \
+               WHERE p.published = ? AND u.age >= ? ORDER BY u.id";
+    let users = ModelQuery::<User>::from_sql(backend, sql, &[QueryValue::Bool(true), QueryValue::I32(35)]).await?;
+
+    let names: Vec<&str> = users.iter().map(|u| u.name.as_str()).collect();
+    assert_eq!(names, vec!["Cat"]);
+    assert_eq!(users[0].age, 50);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_json_field_round_trips() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    create_posts_table(backend).await?;
+    let metadata = serde_json::json!({
+        "theme": "dark",
+        "notifications": { "email": true, "digest": ["mon", "thu"] },
+    });
+    let created = Post::create(backend, &Post { author_id: 1, metadata: metadata.clone(), ..Default::default() }).await?;
+    assert_eq!(created.metadata, metadata);
+
+    let found = Post::find(backend, Value::I64(created.id.unwrap())).await?.unwrap();
+    assert_eq!(found.metadata, metadata);
+    assert_eq!(found.metadata["notifications"]["digest"][1], "thu");
+
+    // Objects and arrays in a JSON row are kept rather than collapsed to NULL
+    let hydrated = Post::from_json(&serde_json::json!({ "id": 7, "author_id": 1, "published": 0, "metadata": ["a", { "b": 1 }] }))?;
+    assert_eq!(hydrated.metadata, serde_json::json!(["a", { "b": 1 }]));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_upsert_inserts_then_updates() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    backend.execute("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, email TEXT NOT NULL UNIQUE, age INTEGER NOT NULL)", &[]).await?;
+    let mut user = User { id: None, name: "Ann".to_string(), email: "ann@example.com".to_string(), age: 30 };
+
+    let inserted = User::upsert(backend, &user, &["email"]).await?;
+    assert_eq!(inserted.age, 30);
+
+    user.age = 31;
+    user.name = "Ann Lee".to_string();
+    let updated = User::upsert(backend, &user, &["email"]).await?;
+    assert_eq!(updated.id, inserted.id);
+    assert_eq!(updated.age, 31);
+    assert_eq!(updated.name, "Ann Lee");
+    assert_eq!(User::query(backend).count().await?, 1);
+
+    // Conflict columns default to the primary key
+    let by_id = User::upsert(backend, &User { age: 32, ..updated }, &[]).await?;
+    assert_eq!(by_id.age, 32);
+    assert_eq!(User::query(backend).count().await?, 1);
+
+    // A conflict column without a value can't identify the row
+    let missing = User::upsert(backend, &user, &[]).await;
+    assert!(matches!(missing, Err(Error::QueryError(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_touch_bumps_updated_at_only() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    create_posts_table(backend).await?;
+    backend.execute("INSERT INTO posts (author_id, published, created_at, updated_at) VALUES (1, 0, '2000-01-01 00:00:00', '2000-01-01 00:00:00')", &[]).await?;
+
+    let post = Post::find(backend, Value::I64(1)).await?.unwrap();
+    post.touch(backend).await?;
+
+    let row = backend.fetch_one_params("SELECT author_id, created_at, updated_at FROM posts WHERE id = 1", &[]).await?.unwrap();
+    assert_eq!(row["author_id"], 1);
+    assert_eq!(row["created_at"], "2000-01-01 00:00:00");
+    let updated_at = row["updated_at"].as_str().unwrap();
+    assert!(updated_at > "2000-01-01 00:00:00", "updated_at not bumped: {}", updated_at);
+    assert!(chrono::NaiveDateTime::parse_from_str(updated_at, "%Y-%m-%d %H:%M:%S").is_ok(), "{}", updated_at);
+
+    // Models without timestamps can't be touched
+    backend.execute("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, email TEXT NOT NULL, age INTEGER NOT NULL)", &[]).await?;
+    let user = User::create(backend, &User { id: None, name: "Ann".to_string(), email: "ann@example.com".to_string(), age: 30 }).await?;
+    assert!(matches!(user.touch(backend).await, Err(Error::QueryError(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_find_many_preserves_input_order() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    backend.execute("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, email TEXT NOT NULL, age INTEGER NOT NULL)", &[]).await?;
+    for i in 1..=8 {
+        User::create(backend, &User { id: None, name: format!("user{}", i), email: format!("user{}@example.com", i), age: 20 + i }).await?;
+    }
+
+    let ids = [Value::I64(7), Value::I32(2), Value::I64(99), Value::I64(5), Value::I32(1), Value::I64(4)];
+    let found = User::find_many(backend, &ids).await?;
+    let found_ids: Vec<i64> = found.iter().map(|u| u.id.unwrap()).collect();
+    assert_eq!(found_ids, vec![7, 2, 5, 1, 4]);
+    assert_eq!(found[0].name, "user7");
+
+    assert!(User::find_many(backend, &[]).await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_refresh_reloads_out_of_band_changes() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    backend.execute("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, email TEXT NOT NULL, age INTEGER NOT NULL)", &[]).await?;
+    let mut user = User::create(backend, &User { id: None, name: "Alice".to_string(), email: "alice@example.com".to_string(), age: 30 }).await?;
+
+    backend.execute(
+        "UPDATE users SET name = ?, age = ? WHERE id = ?",
+        &[QueryValue::String("Alicia".to_string()), QueryValue::I32(31), QueryValue::I64(user.id.unwrap())],
+    ).await?;
+    user.email = "unsaved@example.com".to_string();
+
+    user.refresh(backend).await?;
+    assert_eq!(user.name, "Alicia");
+    assert_eq!(user.age, 31);
+    assert_eq!(user.email, "alice@example.com");
+
+    backend.execute("DELETE FROM users WHERE id = ?", &[QueryValue::I64(user.id.unwrap())]).await?;
+    let err = user.refresh(backend).await.unwrap_err();
+    assert!(err.to_string().contains("no row with id"));
+
+    let mut unsaved = User { id: None, name: "Bob".to_string(), email: "bob@example.com".to_string(), age: 40 };
+    assert!(matches!(unsaved.refresh(backend).await, Err(Error::QueryError(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_with_count_annotates_related_rows() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    backend.execute("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, email TEXT NOT NULL, age INTEGER NOT NULL)", &[]).await?;
+    create_posts_table(backend).await?;
+    for name in ["alice", "bob", "carol"] {
+        User::create(backend, &User { id: None, name: name.to_string(), email: format!("{}@example.com", name), age: 30 }).await?;
+    }
+    for author_id in [1, 1, 1, 3] {
+        backend.execute("INSERT INTO posts (author_id, published) VALUES (?, 1)", &[QueryValue::I64(author_id)]).await?;
+    }
+
+    let query = User::query(backend)
+        .with_count("posts", "author_id", "post_count")
+        .order_by("id", orm::query::OrderDirection::Asc);
+    assert!(query.to_sql()?.contains("(SELECT COUNT(*) FROM posts WHERE posts.author_id = users.id) AS post_count"));
+
+    let rows = backend.fetch_all_params(&query.to_sql()?, &[]).await?;
+    let counts: Vec<i64> = rows.iter().map(|row| row["post_count"].as_i64().unwrap()).collect();
+    assert_eq!(counts, vec![3, 0, 1]);
+
+    let authors = query.get_with_counts().await?;
+    assert_eq!(authors[0].0.name, "alice");
+    assert_eq!(authors[0].1["post_count"], 3);
+    assert_eq!(authors[1].1["post_count"], 0);
+
+    let bad = User::query(backend).with_count("posts; DROP TABLE users", "author_id", "n");
+    assert!(matches!(bad.to_sql(), Err(Error::QueryError(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_exists_by() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    backend.execute("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, email TEXT NOT NULL, age INTEGER NOT NULL)", &[]).await?;
+    let alice = User::create(backend, &User { id: None, name: "Alice".to_string(), email: "alice@example.com".to_string(), age: 30 }).await?;
+
+    assert!(User::exists_by(backend, "email", QueryValue::from("alice@example.com")).await?);
+    assert!(!User::exists_by(backend, "email", QueryValue::from("bob@example.com")).await?);
+
+    assert!(User::exists_by_id(backend, Value::I64(alice.id.unwrap())).await?);
+    assert!(!User::exists_by_id(backend, Value::I64(404)).await?);
+
+    assert!(User::query(backend).where_cmp("age", ">", QueryValue::I32(18)).exists().await?);
+    assert!(!User::query(backend).where_cmp("age", ">", QueryValue::I32(65)).exists().await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_model_query_to_sql_with_params() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+
+    let (sql, params) = User::query(db.backend())
+        .where_eq("email", QueryValue::from("alice@example.com"))
+        .order_by("name", orm::query::OrderDirection::Desc)
+        .limit(10)
+        .to_sql_with_params()?;
+
+    assert_eq!(sql, "SELECT id, name, email, age FROM users WHERE email = ? ORDER BY name DESC LIMIT 10");
+    assert_eq!(params, vec![QueryValue::from("alice@example.com")]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_model_query_when_applies_optional_filters() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    backend.execute("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, email TEXT NOT NULL, age INTEGER NOT NULL)", &[]).await?;
+    for (name, age) in [("alice", 30), ("bob", 17), ("carol", 45)] {
+        User::create(backend, &User { id: None, name: name.to_string(), email: format!("{}@example.com", name), age }).await?;
+    }
+
+    let search = |min_age: Option<i32>| {
+        User::query(backend)
+            .when(min_age.is_some(), |q| q.where_cmp("age", ">=", QueryValue::I32(min_age.unwrap())))
+            .order_by("id", orm::query::OrderDirection::Asc)
+    };
+
+    let everyone: Vec<String> = search(None).get().await?.into_iter().map(|u| u.name).collect();
+    assert_eq!(everyone, vec!["alice", "bob", "carol"]);
+    let adults: Vec<String> = search(Some(18)).get().await?.into_iter().map(|u| u.name).collect();
+    assert_eq!(adults, vec!["alice", "carol"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_model_query_debug_leaves_chain_unchanged() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    backend.execute("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, email TEXT NOT NULL, age INTEGER NOT NULL)", &[]).await?;
+    for (name, age) in [("alice", 30), ("bob", 17), ("carol", 45)] {
+        User::create(backend, &User { id: None, name: name.to_string(), email: format!("{}@example.com", name), age }).await?;
+    }
+
+    let plain = User::query(backend)
+        .where_cmp("age", ">=", QueryValue::I32(18))
+        .order_by("id", orm::query::OrderDirection::Asc);
+    let debugged = User::query(backend)
+        .debug()
+        .where_cmp("age", ">=", QueryValue::I32(18))
+        .debug()
+        .order_by("id", orm::query::OrderDirection::Asc);
+    assert_eq!(debugged.to_sql_with_params()?, plain.to_sql_with_params()?);
+
+    let names: Vec<String> = debugged.get().await?.into_iter().map(|u| u.name).collect();
+    assert_eq!(names, vec!["alice", "carol"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_model_from_values() -> Result<()> {
+    let mut values = HashMap::new();
+    values.insert("id".to_string(), Value::I64(7));
+    values.insert("name".to_string(), Value::String("Alice".to_string()));
+    values.insert("email".to_string(), Value::String("alice@example.com".to_string()));
+    values.insert("age".to_string(), Value::I32(30));
+
+    let user = User::from_values(values)?;
+    assert_eq!(user.id, Some(7));
+    assert_eq!(user.name, "Alice");
+    assert_eq!(user.age, 30);
+
+    // Round trip through to_values
+    let again = User::from_values(user.to_values())?;
+    assert_eq!(again.email, user.email);
+
+    values = HashMap::new();
+    values.insert("name".to_string(), Value::String("Bob".to_string()));
+    assert!(matches!(User::from_values(values), Err(Error::SerializationError(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_or_where_in_mixes_and_or_terms() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    backend.execute("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, email TEXT NOT NULL, age INTEGER NOT NULL)", &[]).await?;
+    for (name, age) in [("alice", 30), ("bob", 17), ("carol", 45), ("dave", 17)] {
+        User::create(backend, &User { id: None, name: name.to_string(), email: format!("{}@example.com", name), age }).await?;
+    }
+
+    // (age = 17 AND name = 'bob') OR id IN (3, 4)
+    let query = User::query(backend)
+        .where_eq("age", QueryValue::I32(17))
+        .where_eq("name", QueryValue::from("bob"))
+        .or_where_in("id", &[QueryValue::I64(3), QueryValue::I64(4)])
+        .order_by("id", orm::query::OrderDirection::Asc);
+    let (sql, params) = query.to_sql_with_params()?;
+    assert!(sql.ends_with("WHERE age = ? AND name = ? OR id IN (?, ?) ORDER BY id ASC"), "{}", sql);
+    assert_eq!(params, vec![QueryValue::I32(17), QueryValue::from("bob"), QueryValue::I64(3), QueryValue::I64(4)]);
+    let names: Vec<String> = query.get().await?.into_iter().map(|u| u.name).collect();
+    assert_eq!(names, vec!["bob", "carol", "dave"]);
+
+    // age = 17 AND (name = 'alice' OR id IN (4))
+    let grouped = User::query(backend)
+        .where_eq("age", QueryValue::I32(17))
+        .where_group(|q| {
+            q.where_eq("name", QueryValue::from("alice"))
+                .or_where_in("id", &[QueryValue::I64(4)]);
+        });
+    let (sql, params) = grouped.to_sql_with_params()?;
+    assert!(sql.ends_with("WHERE age = ? AND (name = ? OR id IN (?))"), "{}", sql);
+    assert_eq!(params, vec![QueryValue::I32(17), QueryValue::from("alice"), QueryValue::I64(4)]);
+    let names: Vec<String> = grouped.get().await?.into_iter().map(|u| u.name).collect();
+    assert_eq!(names, vec!["dave"]);
+
+    // An OR group stands on its own
+    let either = User::query(backend)
+        .where_eq("name", QueryValue::from("alice"))
+        .or_where_group(|q| {
+            q.where_eq("age", QueryValue::I32(17)).where_eq("name", QueryValue::from("dave"));
+        });
+    assert!(either.to_sql()?.ends_with("WHERE name = ? OR (age = ? AND name = ?)"));
+    assert_eq!(either.get().await?.len(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_all_paginated_returns_slice_and_total() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    backend.execute("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, email TEXT NOT NULL, age INTEGER NOT NULL)", &[]).await?;
+    for i in 1..=23 {
+        User::create(backend, &User { id: None, name: format!("user{}", i), email: format!("user{}@example.com", i), age: 20 + i }).await?;
+    }
+
+    let page = User::all_paginated(backend, 2, 10).await?;
+    let ids: Vec<i64> = page.items.iter().map(|u| u.id.unwrap()).collect();
+    assert_eq!(ids, (11..=20).collect::<Vec<i64>>());
+    assert_eq!(page.total, 23);
+    assert_eq!(page.total_pages(), 3);
+    assert!(page.has_next());
+
+    let last = User::all_paginated(backend, 3, 10).await?;
+    assert_eq!(last.items.len(), 3);
+    assert!(!last.has_next());
+
+    // The total counts the filtered rows, not the page
+    let filtered = User::query(backend)
+        .where_cmp("age", ">", QueryValue::I32(40))
+        .order_by("id", orm::query::OrderDirection::Desc)
+        .paginate(1, 2)
+        .await?;
+    assert_eq!(filtered.total, 3);
+    assert_eq!(filtered.items[0].name, "user23");
+
+    assert!(User::all_paginated(backend, 1, 0).await.is_err());
+
+    let empty: Page<User> = Page { items: Vec::new(), total: 5, page: 1, per_page: 0 };
+    assert_eq!(empty.total_pages(), 0);
+    assert!(!empty.has_next());
+
+    Ok(())
+}
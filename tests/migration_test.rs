@@ -0,0 +1,691 @@
+use orm::prelude::*;
+use async_trait::async_trait;
+use orm::migration::{Migration, MigrationRunner, Schema, SchemaOperation};
+use orm::query::builder::Dialect;
+use orm::query::QueryValue;
+use orm::schema::{export_schema, Column, ColumnType, ForeignKey, ForeignKeyAction, Table};
+
+fn users_table(email_type: ColumnType) -> Table {
+    let mut table = Table::new("users");
+    table.add_column(Column::new("id", ColumnType::BigInteger).primary_key().auto_increment());
+    table.add_column(Column::new("email", email_type));
+    table.add_column(Column::new("nickname", ColumnType::Text).nullable(true));
+    table
+}
+
+#[tokio::test]
+async fn test_apply_diff_generates_operations() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+
+    let current = export_schema(vec![users_table(ColumnType::Varchar(100))]);
+
+    let mut users = users_table(ColumnType::Varchar(255));
+    users.add_column(Column::new("age", ColumnType::Integer).nullable(true));
+    let mut posts = Table::new("posts");
+    posts.add_column(Column::new("id", ColumnType::BigInteger).primary_key().auto_increment());
+    posts.add_column(Column::new("title", ColumnType::Text));
+    let mut desired = export_schema(vec![users, posts]);
+    desired.tables[0].columns.retain(|c| c.name != "nickname");
+
+    let diff = current.diff(&desired);
+    let mut schema = Schema::new(db.backend(), Dialect::MySQL);
+    schema.apply_diff(&diff);
+
+    let sql = schema.to_sql()?;
+    assert_eq!(sql.len(), 4);
+    assert!(sql[0].starts_with("CREATE TABLE posts ("));
+    assert_eq!(sql[1], "ALTER TABLE users ADD COLUMN age INT");
+    assert_eq!(sql[2], "ALTER TABLE users MODIFY COLUMN email VARCHAR(255) NOT NULL");
+    assert_eq!(sql[3], "ALTER TABLE users DROP COLUMN nickname");
+
+    // Postgres applies the same nullability and default as MySQL's MODIFY COLUMN
+    let mut postgres_schema = Schema::new(db.backend(), Dialect::Postgres);
+    postgres_schema.apply_diff(&diff);
+    assert_eq!(
+        postgres_schema.to_sql()?[2],
+        "ALTER TABLE users ALTER COLUMN email TYPE VARCHAR(255), ALTER COLUMN email SET NOT NULL, \
+         ALTER COLUMN email DROP DEFAULT"
+    );
+    postgres_schema.modify_column("users", Column::new("age", ColumnType::BigInteger).nullable(true).default("0"));
+    assert_eq!(
+        postgres_schema.to_sql()?[4],
+        "ALTER TABLE users ALTER COLUMN age TYPE BIGINT, ALTER COLUMN age DROP NOT NULL, \
+         ALTER COLUMN age SET DEFAULT 0"
+    );
+
+    // SQLite has no way to alter a column in place
+    let mut sqlite_schema = Schema::new(db.backend(), Dialect::SQLite);
+    sqlite_schema.apply_diff(&diff);
+    assert!(sqlite_schema.to_sql().is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_array_column() -> Result<()> {
+    let mut table = Table::new("posts");
+    table.add_column(Column::new("id", ColumnType::BigInteger).primary_key().auto_increment());
+    table.add_column(Column::new("tag_ids", ColumnType::Array(Box::new(ColumnType::Integer))));
+
+    let postgres = table.to_create_sql(Dialect::Postgres);
+    assert!(postgres.contains("tag_ids INTEGER[] NOT NULL"), "{}", postgres);
+
+    // Without native arrays the column holds a JSON array
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+    backend.execute(&table.to_create_sql(Dialect::SQLite), &[]).await?;
+    backend
+        .execute("INSERT INTO posts (tag_ids) VALUES (?)", &[QueryValue::from(vec![1, 2, 3])])
+        .await?;
+
+    let row = backend.fetch_one_params("SELECT tag_ids FROM posts", &[]).await?.unwrap();
+    assert_eq!(row.get("tag_ids").and_then(|v| v.as_str()), Some("[1,2,3]"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_check_constraints_reject_invalid_rows() -> Result<()> {
+    let mut table = Table::new("accounts");
+    table.add_column(Column::new("id", ColumnType::BigInteger).primary_key().auto_increment());
+    table.add_column(Column::new("age", ColumnType::Integer).check("age >= 0"));
+    table.add_column(Column::new("balance", ColumnType::Integer));
+    table.add_check("balance_non_negative", "balance >= 0");
+
+    let sql = table.to_create_sql(Dialect::SQLite);
+    assert!(sql.contains("age INTEGER NOT NULL CHECK (age >= 0)"), "{}", sql);
+    assert!(sql.contains("CONSTRAINT balance_non_negative CHECK (balance >= 0)"), "{}", sql);
+
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+    backend.execute(&sql, &[]).await?;
+
+    let insert = "INSERT INTO accounts (age, balance) VALUES (?, ?)";
+    backend.execute(insert, &[QueryValue::I32(30), QueryValue::I32(10)]).await?;
+
+    for params in [[QueryValue::I32(-1), QueryValue::I32(10)], [QueryValue::I32(30), QueryValue::I32(-5)]] {
+        let err = backend.execute(insert, &params).await.unwrap_err();
+        assert!(matches!(err, Error::ConstraintViolation { kind: ConstraintKind::Check, .. }), "{:?}", err);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_composite_unique_constraint() -> Result<()> {
+    let mut table = Table::new("post_tags");
+    table.add_column(Column::new("post_id", ColumnType::BigInteger));
+    table.add_column(Column::new("tag_id", ColumnType::BigInteger));
+    table.add_unique("uq_post_tags", vec!["post_id".to_string(), "tag_id".to_string()]);
+
+    let sql = table.to_create_sql(Dialect::SQLite);
+    assert!(sql.contains("CONSTRAINT uq_post_tags UNIQUE (post_id, tag_id)"), "{}", sql);
+
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+    backend.execute(&sql, &[]).await?;
+
+    let insert = "INSERT INTO post_tags (post_id, tag_id) VALUES (?, ?)";
+    backend.execute(insert, &[QueryValue::I64(1), QueryValue::I64(1)]).await?;
+    backend.execute(insert, &[QueryValue::I64(1), QueryValue::I64(2)]).await?;
+
+    let err = backend.execute(insert, &[QueryValue::I64(1), QueryValue::I64(1)]).await.unwrap_err();
+    assert!(matches!(err, Error::ConstraintViolation { kind: ConstraintKind::Unique, .. }), "{:?}", err);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_foreign_key_builder_actions() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let mut schema = Schema::new(db.backend(), Dialect::SQLite);
+    schema.create_table("comments", |table| {
+        table.id("id");
+        table.big_integer("post_id");
+        table
+            .foreign_key_to("post_id", "posts", "id")
+            .on_delete(ForeignKeyAction::Cascade)
+            .on_update(ForeignKeyAction::Restrict);
+    });
+
+    let sql = schema.to_sql()?;
+    assert!(
+        sql[0].contains("FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE ON UPDATE RESTRICT"),
+        "{}",
+        sql[0]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_foreign_key_constraint_names() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let mut schema = Schema::new(db.backend(), Dialect::SQLite);
+    schema.create_table("comments", |table| {
+        table.id("id");
+        table.big_integer("post_id");
+        table.big_integer("author_id");
+        table.foreign_key_to("post_id", "posts", "id");
+        table
+            .foreign_key_to("author_id", "users", "id")
+            .name("comments_author_fk")
+            .on_delete(ForeignKeyAction::SetNull);
+    });
+
+    let sql = schema.to_sql()?;
+    assert!(sql[0].contains("CONSTRAINT fk_comments_post_id FOREIGN KEY (post_id) REFERENCES posts(id)"), "{}", sql[0]);
+    assert!(
+        sql[0].contains("CONSTRAINT comments_author_fk FOREIGN KEY (author_id) REFERENCES users(id) ON DELETE SET NULL"),
+        "{}",
+        sql[0]
+    );
+
+    let mut table = Table::new("orders");
+    table.add_column(Column::new("customer_id", ColumnType::BigInteger));
+    table.add_foreign_key(ForeignKey {
+        name: None,
+        column: "customer_id".to_string(),
+        references_table: "customers".to_string(),
+        references_column: "id".to_string(),
+        on_delete: None,
+        on_update: None,
+    });
+    assert!(!table.to_create_sql(Dialect::SQLite).contains("CONSTRAINT"));
+
+    Ok(())
+}
+
+struct CreateRoles;
+
+#[async_trait]
+impl Migration for CreateRoles {
+    fn name(&self) -> &str {
+        "create_roles"
+    }
+
+    fn version(&self) -> i64 {
+        20240101000000
+    }
+
+    async fn up(&self, schema: &mut Schema) -> Result<()> {
+        schema.create_table("roles", |table| {
+            table.id("id");
+            table.string("name", 50);
+        });
+        schema.seed(
+            "roles",
+            &["name"],
+            vec![vec![QueryValue::from("admin")], vec![QueryValue::from("member")]],
+        );
+        Ok(())
+    }
+
+    async fn down(&self, schema: &mut Schema) -> Result<()> {
+        schema.drop_table("roles");
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_migration_seeds_reference_data() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let mut runner = MigrationRunner::new(db.backend(), Dialect::SQLite);
+    runner.add_migration(Box::new(CreateRoles));
+    runner.run_pending(db.backend()).await?;
+
+    let rows = db.backend().fetch_all_params("SELECT name FROM roles ORDER BY id", &[]).await?;
+    let names: Vec<&str> = rows.iter().filter_map(|r| r.get("name").and_then(|v| v.as_str())).collect();
+    assert_eq!(names, vec!["admin", "member"]);
+
+    // A malformed seed fails before anything is executed
+    let mut schema = Schema::new(db.backend(), Dialect::SQLite);
+    schema.create_table("permissions", |table| {
+        table.id("id");
+    });
+    schema.seed("permissions", &["id", "name"], vec![vec![QueryValue::I64(1)]]);
+    assert!(schema.execute(db.backend()).await.is_err());
+    assert!(db.backend().fetch_all_params("SELECT * FROM permissions", &[]).await.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reset_rebuilds_schema() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+    let mut runner = MigrationRunner::new(backend, Dialect::SQLite);
+    runner.add_migration(Box::new(CreateRoles));
+    runner.run_pending(backend).await?;
+    backend.execute("INSERT INTO roles (name) VALUES (?)", &[QueryValue::from("guest")]).await?;
+
+    let snapshot = || async {
+        let roles = backend.fetch_all_params("SELECT id, name FROM roles ORDER BY id", &[]).await?;
+        let versions = backend.fetch_all_params("SELECT version, name FROM migrations", &[]).await?;
+        Ok::<_, Error>((roles, versions))
+    };
+
+    runner.reset(backend).await?;
+    let first = snapshot().await?;
+    runner.reset(backend).await?;
+    let second = snapshot().await?;
+
+    assert_eq!(first, second);
+    assert_eq!(first.0.len(), 2);
+    assert_eq!(first.1.len(), 1);
+
+    Ok(())
+}
+
+struct CountedMigration {
+    runs: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+}
+
+#[async_trait]
+impl Migration for CountedMigration {
+    fn name(&self) -> &str {
+        "create_audit_log"
+    }
+
+    fn version(&self) -> i64 {
+        20240102000000
+    }
+
+    async fn up(&self, schema: &mut Schema) -> Result<()> {
+        self.runs.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        // Widen the race window between reading and recording versions
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        schema.create_table("audit_log", |table| {
+            table.id("id");
+            table.text("message");
+        });
+        Ok(())
+    }
+
+    async fn down(&self, schema: &mut Schema) -> Result<()> {
+        schema.drop_table("audit_log");
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_concurrent_runners_apply_migrations_once() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("orm_migration_lock_{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let url = format!("sqlite://{}", dir.join("app.db").display());
+
+    let runs = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let run = |url: String| {
+        let runs = runs.clone();
+        async move {
+            let db = Database::connect(&url).await?;
+            let mut runner = MigrationRunner::new(db.backend(), Dialect::SQLite);
+            runner.add_migration(Box::new(CountedMigration { runs }));
+            runner.run_pending(db.backend()).await?;
+            Ok::<_, Error>(db)
+        }
+    };
+
+    let (first, second) = tokio::join!(run(url.clone()), run(url.clone()));
+    let db = first?;
+    second?;
+
+    assert_eq!(runs.load(std::sync::atomic::Ordering::SeqCst), 1);
+    let rows = db.backend().fetch_all_params("SELECT version FROM migrations", &[]).await?;
+    assert_eq!(rows.len(), 1);
+
+    std::fs::remove_dir_all(&dir).ok();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_schema_exposes_planned_operations() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let mut schema = Schema::new(db.backend(), Dialect::SQLite);
+    schema.create_table("tags", |table| {
+        table.id("id");
+        table.string("slug", 50);
+    });
+    schema.add_column("posts", Column::new("tag_id", ColumnType::BigInteger).nullable(true));
+    schema.create_index("posts", "idx_posts_tag_id", vec!["tag_id".to_string()], false);
+    schema.drop_table("legacy_tags");
+
+    let operations = schema.operations();
+    assert_eq!(operations.len(), 4);
+    assert!(matches!(&operations[0], SchemaOperation::CreateTable(table) if table.name() == "tags"));
+    assert!(matches!(&operations[1], SchemaOperation::AddColumn { table, column } if table == "posts" && column.name() == "tag_id"));
+    assert!(matches!(&operations[2], SchemaOperation::CreateIndex { unique: false, .. }));
+    assert!(matches!(&operations[3], SchemaOperation::DropTable(name) if name == "legacy_tags"));
+
+    // Nothing has run yet
+    assert!(db.backend().fetch_all_params("SELECT * FROM tags", &[]).await.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_foreign_key_set_default_action() {
+    let mut table = Table::new("orders");
+    table.add_column(Column::new("id", ColumnType::BigInteger).primary_key().auto_increment());
+    table.add_column(Column::new("status_id", ColumnType::BigInteger).default("1"));
+    table.add_foreign_key(ForeignKey {
+        name: None,
+        column: "status_id".to_string(),
+        references_table: "statuses".to_string(),
+        references_column: "id".to_string(),
+        on_delete: Some(ForeignKeyAction::SetDefault),
+        on_update: None,
+    });
+
+    let sql = table.to_create_sql(Dialect::MySQL);
+    assert!(sql.contains("FOREIGN KEY (status_id) REFERENCES statuses(id) ON DELETE SET DEFAULT"), "{}", sql);
+}
+
+#[tokio::test]
+async fn test_set_primary_key_emits_table_constraint() -> Result<()> {
+    let mut table = Table::new("countries");
+    table.add_column(Column::new("code", ColumnType::Varchar(2)));
+    table.add_column(Column::new("name", ColumnType::Text));
+    table.set_primary_key("code");
+    table.add_unique("uq_countries_code", vec!["code".to_string()]);
+
+    let sql = table.to_create_sql(Dialect::SQLite);
+    assert!(sql.contains("PRIMARY KEY (code)"), "{}", sql);
+    assert!(!sql.contains("UNIQUE"), "{}", sql);
+
+    let db = Database::connect("sqlite::memory:").await?;
+    db.backend().execute(&sql, &[]).await?;
+    db.backend().execute("INSERT INTO countries (code, name) VALUES ('nz', 'New Zealand')", &[]).await?;
+    assert!(db.backend().execute("INSERT INTO countries (code, name) VALUES ('nz', 'Aotearoa')", &[]).await.is_err());
+
+    // A column-level primary key is not declared a second time
+    let mut table = Table::new("widgets");
+    table.add_column(Column::new("id", ColumnType::BigInteger).primary_key());
+    table.set_primary_key("id");
+    assert_eq!(table.to_create_sql(Dialect::SQLite).matches("PRIMARY KEY").count(), 1);
+
+    // Nor is set_primary_key then, so a UNIQUE on that column is still needed
+    table.add_column(Column::new("code", ColumnType::Varchar(2)));
+    table.set_primary_key("code");
+    table.add_unique("uq_widgets_code", vec!["code".to_string()]);
+    let sql = table.to_create_sql(Dialect::SQLite);
+    assert!(!sql.contains("PRIMARY KEY (code)"), "{}", sql);
+    assert!(sql.contains("CONSTRAINT uq_widgets_code UNIQUE (code)"), "{}", sql);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_auto_increment_primary_key_ddl() -> Result<()> {
+    let mut table = Table::new("widgets");
+    table.add_column(Column::new("id", ColumnType::BigInteger).primary_key().auto_increment().unique());
+    table.add_column(Column::new("code", ColumnType::Varchar(20)).unique());
+    // Redundant with the primary key, so it is not rendered
+    table.add_unique("uq_widgets_id", vec!["id".to_string()]);
+
+    let expected = [
+        (Dialect::SQLite, "CREATE TABLE widgets (\n  id INTEGER PRIMARY KEY AUTOINCREMENT,\n  code VARCHAR(20) NOT NULL UNIQUE\n)"),
+        (Dialect::MySQL, "CREATE TABLE widgets (\n  id BIGINT PRIMARY KEY AUTO_INCREMENT,\n  code VARCHAR(20) NOT NULL UNIQUE\n)"),
+        (Dialect::Postgres, "CREATE TABLE widgets (\n  id BIGSERIAL PRIMARY KEY,\n  code VARCHAR(20) NOT NULL UNIQUE\n)"),
+    ];
+    for (dialect, ddl) in expected {
+        assert_eq!(table.to_create_sql(dialect), ddl);
+    }
+
+    // `TableBuilder::id` renders the same primary key
+    let db = Database::connect("sqlite::memory:").await?;
+    let mut schema = Schema::new(db.backend(), Dialect::SQLite);
+    schema.create_table("gadgets", |table| {
+        table.id("id");
+    });
+    assert_eq!(schema.to_sql()?, vec!["CREATE TABLE gadgets (\n  id INTEGER PRIMARY KEY AUTOINCREMENT\n)"]);
+
+    db.backend().execute(&table.to_create_sql(Dialect::SQLite), &[]).await?;
+    schema.execute(db.backend()).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_column_comments() -> Result<()> {
+    let mut table = Table::new("invoices");
+    table.add_column(Column::new("id", ColumnType::BigInteger).primary_key());
+    table.add_column(Column::new("total", ColumnType::Integer).comment("Amount in cents, it's never negative"));
+
+    let mysql = table.to_create_sql(Dialect::MySQL);
+    assert!(mysql.contains("total INT NOT NULL COMMENT 'Amount in cents, it''s never negative'"), "{}", mysql);
+    let sqlite = table.to_create_sql(Dialect::SQLite);
+    assert!(sqlite.contains("total INTEGER NOT NULL /* Amount in cents, it's never negative */"), "{}", sqlite);
+    assert!(!table.to_create_sql(Dialect::Postgres).contains("Amount"));
+
+    // SQLite keeps the comment in the stored table definition
+    let db = Database::connect("sqlite::memory:").await?;
+    db.backend().execute(&sqlite, &[]).await?;
+    let stored = db.backend()
+        .fetch_one_params("SELECT sql FROM sqlite_master WHERE name = 'invoices'", &[])
+        .await?
+        .unwrap();
+    assert!(stored["sql"].as_str().unwrap().contains("Amount in cents"));
+
+    let export = export_schema(vec![table]);
+    assert_eq!(export.tables[0].columns[1].comment.as_deref(), Some("Amount in cents, it's never negative"));
+
+    Ok(())
+}
+
+/// Connect to the server named by `MYSQL_URL`, for tests ignored unless one is available
+async fn connect_mysql() -> Result<Database> {
+    let url = std::env::var("MYSQL_URL").expect("MYSQL_URL must name a MySQL server");
+    Database::connect(&url).await
+}
+
+#[tokio::test]
+#[ignore = "requires MYSQL_URL"]
+async fn test_mysql_column_comment_introspection() -> Result<()> {
+    let db = connect_mysql().await?;
+    let backend = db.backend();
+
+    let mut table = Table::new("commented_invoices");
+    table.add_column(Column::new("id", ColumnType::BigInteger).primary_key());
+    table.add_column(Column::new("total", ColumnType::Integer).comment("Amount in cents"));
+    backend.execute_batch("DROP TABLE IF EXISTS commented_invoices;").await?;
+    backend.execute(&table.to_create_sql(Dialect::MySQL), &[]).await?;
+
+    let row = backend.fetch_one_params(
+        "SELECT COLUMN_COMMENT AS comment FROM information_schema.columns \
+         WHERE table_schema = DATABASE() AND table_name = ? AND column_name = ?",
+        &[QueryValue::from("commented_invoices"), QueryValue::from("total")],
+    ).await?.unwrap();
+    assert_eq!(row["comment"], "Amount in cents");
+
+    backend.execute_batch("DROP TABLE commented_invoices;").await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_drop_table_cascade() -> Result<()> {
+    use orm::connection::ConnectOptions;
+
+    let db = Database::connect_with("sqlite::memory:", &ConnectOptions::new().max_connections(1)).await?;
+    let backend = db.backend();
+
+    let render = |dialect| {
+        let mut schema = Schema::new(backend, dialect);
+        schema.drop_table_cascade("authors");
+        schema.to_sql()
+    };
+    assert_eq!(render(Dialect::Postgres)?, vec!["DROP TABLE IF EXISTS authors CASCADE"]);
+    assert_eq!(render(Dialect::MySQL)?, vec![
+        "SET FOREIGN_KEY_CHECKS = 0",
+        "DROP TABLE IF EXISTS authors",
+        "SET FOREIGN_KEY_CHECKS = 1",
+    ]);
+    assert_eq!(render(Dialect::SQLite)?, vec!["DROP TABLE IF EXISTS authors"]);
+
+    backend.execute_batch(
+        "CREATE TABLE authors (id INTEGER PRIMARY KEY); \
+         CREATE TABLE books (id INTEGER PRIMARY KEY, author_id INTEGER REFERENCES authors(id)); \
+         INSERT INTO authors (id) VALUES (1); \
+         INSERT INTO books (id, author_id) VALUES (1, 1);",
+    ).await?;
+
+    // On SQLite, foreign keys are turned off on the connection before the migration runs
+    let mut schema = Schema::new(backend, Dialect::SQLite);
+    schema.drop_table_cascade("authors");
+    assert!(schema.execute(backend).await.is_err());
+
+    backend.execute("PRAGMA foreign_keys = OFF", &[]).await?;
+    schema.execute(backend).await?;
+    let remaining = backend.fetch_all_params("SELECT name FROM sqlite_master WHERE type = 'table' ORDER BY name", &[]).await?;
+    assert_eq!(remaining, vec![serde_json::json!({"name": "books"})]);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires MYSQL_URL"]
+async fn test_mysql_drop_table_cascade() -> Result<()> {
+    let db = connect_mysql().await?;
+    let backend = db.backend();
+
+    backend.execute_batch(
+        "DROP TABLE IF EXISTS cascade_books; DROP TABLE IF EXISTS cascade_authors; \
+         CREATE TABLE cascade_authors (id BIGINT PRIMARY KEY); \
+         CREATE TABLE cascade_books (id BIGINT PRIMARY KEY, author_id BIGINT, \
+           FOREIGN KEY (author_id) REFERENCES cascade_authors(id));",
+    ).await?;
+
+    let mut schema = Schema::new(backend, Dialect::MySQL);
+    schema.drop_table_cascade("cascade_authors");
+    schema.execute(backend).await?;
+
+    // A failed drop still turns foreign key checks back on for the session
+    let mut failing = Schema::new(backend, Dialect::MySQL);
+    failing.drop_table_cascade("not a table");
+    let mut tx = backend.begin_transaction().await?;
+    assert!(failing.execute_in(&mut tx).await.is_err());
+    let checks = tx.fetch_one_params("SELECT @@FOREIGN_KEY_CHECKS AS checks", &[]).await?;
+    assert_eq!(checks.and_then(|row| row.get("checks").and_then(|v| v.as_i64())), Some(1));
+    tx.rollback().await?;
+
+    backend.execute_batch("DROP TABLE cascade_books;").await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_table_builder_binary_and_small_integers() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    let build = |dialect| {
+        let mut schema = Schema::new(backend, dialect);
+        schema.create_table("attachments", |table| {
+            table.id("id");
+            table.binary("data");
+            table.small_integer("width");
+            table.tiny_integer("flags");
+        });
+        schema
+    };
+
+    let sqlite = build(Dialect::SQLite);
+    assert_eq!(sqlite.to_sql()?, vec![
+        "CREATE TABLE attachments (\n  id INTEGER PRIMARY KEY AUTOINCREMENT,\n  data BLOB NOT NULL,\n  \
+         width SMALLINT NOT NULL,\n  flags TINYINT NOT NULL\n)",
+    ]);
+    let postgres = build(Dialect::Postgres).to_sql()?;
+    assert!(postgres[0].contains("data BYTEA NOT NULL"), "{}", postgres[0]);
+    assert!(postgres[0].contains("flags SMALLINT NOT NULL"), "{}", postgres[0]);
+
+    sqlite.execute(backend).await?;
+    backend.execute("INSERT INTO attachments (data, width, flags) VALUES (x'89504e47', ?, ?)", &[
+        QueryValue::I32(640), QueryValue::I32(3),
+    ]).await?;
+    let row = backend.fetch_one_params("SELECT typeof(data) AS kind, width FROM attachments", &[]).await?.unwrap();
+    assert_eq!(row["kind"], "blob");
+    assert_eq!(row["width"], 640);
+
+    Ok(())
+}
+
+struct CreateAuditTrigger;
+
+#[async_trait]
+impl Migration for CreateAuditTrigger {
+    fn name(&self) -> &str {
+        "create_audit_trigger"
+    }
+
+    fn version(&self) -> i64 {
+        20240103000000
+    }
+
+    async fn up(&self, schema: &mut Schema) -> Result<()> {
+        schema.create_table("orders", |table| {
+            table.id("id");
+            table.integer("total");
+        });
+        schema.create_table("order_audit", |table| {
+            table.id("id");
+            table.big_integer("order_id");
+        });
+        schema.raw(
+            "CREATE TRIGGER orders_audit AFTER INSERT ON orders \
+             BEGIN INSERT INTO order_audit (order_id) VALUES (NEW.id); END",
+        );
+        Ok(())
+    }
+
+    async fn down(&self, schema: &mut Schema) -> Result<()> {
+        schema.raw("DROP TRIGGER IF EXISTS orders_audit");
+        schema.drop_table("order_audit");
+        schema.drop_table("orders");
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_raw_schema_operation_runs_in_order() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+    let mut runner = MigrationRunner::new(backend, Dialect::SQLite);
+    runner.add_migration(Box::new(CreateAuditTrigger));
+    runner.run_pending(backend).await?;
+
+    backend.execute("INSERT INTO orders (total) VALUES (?), (?)", &[QueryValue::I32(10), QueryValue::I32(20)]).await?;
+    let audited = backend.fetch_all_params("SELECT order_id FROM order_audit ORDER BY id", &[]).await?;
+    assert_eq!(audited, vec![serde_json::json!({"order_id": 1}), serde_json::json!({"order_id": 2})]);
+
+    // A failing raw statement rolls back the operations queued before it
+    let mut schema = Schema::new(backend, Dialect::SQLite);
+    schema.create_table("refunds", |table| {
+        table.id("id");
+    });
+    schema.raw("CREATE VIEW broken AS SELECT * FROM");
+    assert!(matches!(schema.operations().last(), Some(SchemaOperation::Raw(_))));
+    assert!(schema.execute(backend).await.is_err());
+    assert!(backend.fetch_all_params("SELECT * FROM refunds", &[]).await.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_applied_migrations_report_executed_at() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+    let mut runner = MigrationRunner::new(backend, Dialect::SQLite);
+    assert!(runner.applied_migrations(backend).await?.is_empty());
+
+    runner.add_migration(Box::new(CreateRoles));
+    runner.run_pending(backend).await?;
+
+    let applied = runner.applied_migrations(backend).await?;
+    assert_eq!(applied.len(), 1);
+    let (version, name, executed_at) = &applied[0];
+    assert_eq!(*version, 20240101000000);
+    assert_eq!(name, "create_roles");
+    assert!(!executed_at.is_empty());
+    assert!(chrono::NaiveDateTime::parse_from_str(executed_at, "%Y-%m-%d %H:%M:%S").is_ok(), "{}", executed_at);
+
+    Ok(())
+}
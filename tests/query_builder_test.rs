@@ -0,0 +1,869 @@
+use orm::prelude::*;
+use orm::query::builder::{Dialect, MySQLQueryBuilder, QueryBuilderEnum, SQLiteQueryBuilder};
+use orm::query::QueryValue;
+use orm::schema::ColumnType;
+
+#[test]
+fn test_builder_clone_branches_independently() -> Result<()> {
+    let mut base = QueryBuilderEnum::new(Dialect::SQLite);
+    base.select(&[Column::new("id", ColumnType::BigInteger), Column::new("name", ColumnType::Text)])
+        .from("users");
+
+    let mut adults = base.clone();
+    adults.where_eq("age", QueryValue::I32(18));
+
+    assert_eq!(base.build()?, "SELECT id, name FROM users");
+    assert!(base.params().is_empty());
+    assert_eq!(adults.build()?, "SELECT id, name FROM users WHERE age = ?");
+    assert_eq!(adults.params(), &[QueryValue::I32(18)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_to_debug_sql_interpolates_params() {
+    let mut builder = QueryBuilderEnum::new(Dialect::SQLite);
+    builder
+        .select(&[Column::new("id", ColumnType::BigInteger)])
+        .from("users")
+        .where_eq("name", QueryValue::String("O'Brien".to_string()))
+        .where_eq("age", QueryValue::I32(30))
+        .where_eq("deleted_at", QueryValue::Null);
+
+    assert_eq!(
+        builder.to_debug_sql(),
+        "SELECT id FROM users WHERE name = 'O''Brien' AND age = 30 AND deleted_at = NULL"
+    );
+}
+
+#[test]
+fn test_having_params_follow_where_params() -> Result<()> {
+    let mut builder = QueryBuilderEnum::new(Dialect::SQLite);
+    builder
+        .select(&[
+            Column::new("customer_id", ColumnType::BigInteger),
+            Column::new("SUM(amount) as total", ColumnType::Double),
+        ])
+        .from("orders")
+        .group_by(&["customer_id"])
+        .having_gt("SUM(amount)", QueryValue::F64(100.0))
+        .where_eq("status", QueryValue::String("paid".to_string()))
+        .limit(5);
+
+    assert_eq!(
+        builder.build()?,
+        "SELECT customer_id, SUM(amount) as total FROM orders WHERE status = ? GROUP BY customer_id HAVING SUM(amount) > ? LIMIT 5"
+    );
+    assert_eq!(builder.params(), &[QueryValue::String("paid".to_string()), QueryValue::F64(100.0)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_having_replaces_and_and_having_combines() -> Result<()> {
+    let mut builder = QueryBuilderEnum::new(Dialect::SQLite);
+    builder
+        .select(&[Column::new("customer_id", ColumnType::BigInteger)])
+        .from("orders")
+        .where_eq("status", QueryValue::String("paid".to_string()))
+        .group_by(&["customer_id"])
+        .having_gt("SUM(amount)", QueryValue::F64(100.0))
+        .having("COUNT(*) > 1")
+        .and_having("MAX(amount) < 500");
+
+    assert_eq!(
+        builder.build()?,
+        "SELECT customer_id FROM orders WHERE status = ? GROUP BY customer_id HAVING COUNT(*) > 1 AND MAX(amount) < 500"
+    );
+    assert_eq!(builder.params(), &[QueryValue::String("paid".to_string())]);
+
+    Ok(())
+}
+
+#[test]
+fn test_join_params_precede_where_params() -> Result<()> {
+    let mut builder = QueryBuilderEnum::new(Dialect::SQLite);
+    builder
+        .select(&[Column::new("users.name", ColumnType::Text), Column::new("posts.title", ColumnType::Text)])
+        .from("users")
+        .where_eq("users.active", QueryValue::Bool(true))
+        .join_on_params(
+            "posts",
+            "posts.user_id = users.id AND posts.published = ?",
+            &[QueryValue::Bool(true)],
+            JoinType::Inner,
+        )
+        .where_eq("users.age", QueryValue::I32(21));
+
+    assert_eq!(
+        builder.build()?,
+        "SELECT users.name, posts.title FROM users INNER JOIN posts ON posts.user_id = users.id AND posts.published = ? WHERE users.active = ? AND users.age = ?"
+    );
+    assert_eq!(builder.params(), &[QueryValue::Bool(true), QueryValue::Bool(true), QueryValue::I32(21)]);
+
+    Ok(())
+}
+
+fn build_demo_report_query(builder: &mut QueryBuilderEnum) -> Result<String> {
+    builder
+        .distinct()
+        .select(&[
+            Column::new("categories.name", ColumnType::Text),
+            Column::new("COUNT(DISTINCT products.id) as product_count", ColumnType::BigInteger),
+            Column::new("SUM(order_items.quantity) as total_sold", ColumnType::BigInteger),
+        ])
+        .from("categories")
+        .inner_join("products", "products.category_id = categories.id")
+        .left_join("order_items", "order_items.product_id = products.id")
+        .where_eq("categories.active", QueryValue::Bool(true))
+        .group_by(&["categories.id", "categories.name"])
+        .having("COUNT(DISTINCT products.id) > 0")
+        .order_by("total_sold", OrderDirection::Desc)
+        .limit(10)
+        .offset(0)
+        .build()
+}
+
+#[test]
+fn test_all_clauses_render_for_both_dialects() -> Result<()> {
+    let expected = "SELECT DISTINCT categories.name, COUNT(DISTINCT products.id) as product_count, \
+        SUM(order_items.quantity) as total_sold FROM categories \
+        INNER JOIN products ON products.category_id = categories.id \
+        LEFT JOIN order_items ON order_items.product_id = products.id \
+        WHERE categories.active = ? GROUP BY categories.id, categories.name \
+        HAVING COUNT(DISTINCT products.id) > 0 ORDER BY total_sold DESC LIMIT 10 OFFSET 0";
+
+    let mut sqlite: SQLiteQueryBuilder = QueryBuilderEnum::new(Dialect::SQLite);
+    assert_eq!(build_demo_report_query(&mut sqlite)?, expected);
+    assert_eq!(sqlite.params().len(), 1);
+
+    let mut mysql: MySQLQueryBuilder = QueryBuilderEnum::new(Dialect::MySQL);
+    assert_eq!(build_demo_report_query(&mut mysql)?, expected);
+    assert_eq!(mysql.params().len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_query_value_equality_and_display() {
+    let mut builder = QueryBuilderEnum::new(Dialect::MySQL);
+    builder.from("users").where_eq("age", QueryValue::I32(18));
+    assert_eq!(builder.params(), &[QueryValue::I32(18)]);
+
+    assert_eq!(QueryValue::Null.to_string(), "NULL");
+    assert_eq!(QueryValue::I64(42).to_string(), "42");
+    assert_eq!(QueryValue::String("it's".to_string()).to_string(), "'it''s'");
+}
+
+#[test]
+fn test_query_value_from_conversions() {
+    assert_eq!(QueryValue::from(18), QueryValue::I32(18));
+    assert_eq!(QueryValue::from(18i64), QueryValue::I64(18));
+    assert_eq!(QueryValue::from(true), QueryValue::Bool(true));
+    assert_eq!(QueryValue::from(1.5), QueryValue::F64(1.5));
+    assert_eq!(QueryValue::from("a".to_string()), QueryValue::String("a".to_string()));
+    assert_eq!(QueryValue::from("b"), QueryValue::String("b".to_string()));
+    assert_eq!(QueryValue::from(Some(5)), QueryValue::I32(5));
+    assert_eq!(QueryValue::from(None::<String>), QueryValue::Null);
+    assert_eq!(QueryValue::from(Value::I64(9)), QueryValue::I64(9));
+
+    let mut builder = QueryBuilderEnum::new(Dialect::SQLite);
+    builder.from("users").where_eq("age", 18.into());
+    assert_eq!(builder.params(), &[QueryValue::I32(18)]);
+}
+
+fn build_deactivate_update(dialect: Dialect) -> Result<(String, String)> {
+    let mut update = QueryBuilderEnum::new(dialect);
+    // WHERE is added before SET; numbering must still follow statement order
+    update.update("users")
+        .where_eq("id", QueryValue::I64(7))
+        .set_param("active", QueryValue::Bool(false))
+        .set_param("note", QueryValue::String("what?".to_string()));
+
+    let mut insert = QueryBuilderEnum::new(dialect);
+    insert.insert_into("users", &["name", "age"])
+        .values_params(&[QueryValue::from("Ann"), QueryValue::I32(30)])
+        .values_params(&[QueryValue::from("Ben"), QueryValue::I32(40)]);
+
+    Ok((update.build()?, insert.build()?))
+}
+
+#[test]
+fn test_dialect_placeholders() -> Result<()> {
+    let (sqlite_update, sqlite_insert) = build_deactivate_update(Dialect::SQLite)?;
+    let (mysql_update, mysql_insert) = build_deactivate_update(Dialect::MySQL)?;
+    let (pg_update, pg_insert) = build_deactivate_update(Dialect::Postgres)?;
+
+    assert_eq!(
+        sqlite_update,
+        "UPDATE users SET active = ?, note = ? WHERE id = ?"
+    );
+    assert_eq!(sqlite_update, mysql_update);
+    assert_eq!(
+        pg_update,
+        "UPDATE users SET active = $1, note = $2 WHERE id = $3"
+    );
+
+    assert_eq!(sqlite_insert, "INSERT INTO users (name, age) VALUES (?, ?), (?, ?)");
+    assert_eq!(sqlite_insert, mysql_insert);
+    assert_eq!(pg_insert, "INSERT INTO users (name, age) VALUES ($1, $2), ($3, $4)");
+
+    let mut builder = QueryBuilderEnum::new(Dialect::Postgres);
+    builder.from("users")
+        .where_eq("id", QueryValue::I64(7))
+        .having_params("MAX(note) <> 'who?' AND COUNT(*) > ?", &[QueryValue::I32(1)]);
+    assert_eq!(
+        builder.build()?,
+        "SELECT * FROM users WHERE id = $1 HAVING MAX(note) <> 'who?' AND COUNT(*) > $2"
+    );
+    assert_eq!(
+        builder.to_debug_sql(),
+        "SELECT * FROM users WHERE id = 7 HAVING MAX(note) <> 'who?' AND COUNT(*) > 1"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_named_params_expand_to_positions() -> Result<()> {
+    let mut builder = QueryBuilderEnum::new(Dialect::SQLite);
+    builder.from("scores")
+        .where_eq("season", QueryValue::I32(2024))
+        .where_raw("(home >= :min OR away >= :min) AND label <> ':min'")
+        .group_by(&["team"])
+        .having_params("COUNT(*) > ? AND SUM(home) >= :min", &[QueryValue::I32(3)])
+        .bind_named("min", QueryValue::I32(10));
+
+    assert_eq!(
+        builder.build()?,
+        "SELECT * FROM scores WHERE season = ? AND (home >= ? OR away >= ?) AND label <> ':min' \
+         GROUP BY team HAVING COUNT(*) > ? AND SUM(home) >= ?"
+    );
+    assert_eq!(
+        builder.params(),
+        &[
+            QueryValue::I32(2024),
+            QueryValue::I32(10),
+            QueryValue::I32(10),
+            QueryValue::I32(3),
+            QueryValue::I32(10),
+        ]
+    );
+
+    let mut unbound = QueryBuilderEnum::new(Dialect::Postgres);
+    unbound.from("scores").where_raw("home >= :min AND kind = 'x'::text");
+    assert!(unbound.build().is_err());
+    unbound.bind_named(":min", QueryValue::I32(1));
+    assert_eq!(unbound.build()?, "SELECT * FROM scores WHERE home >= $1 AND kind = 'x'::text");
+
+    Ok(())
+}
+
+#[test]
+fn test_raw_conditions_reject_unbalanced_placeholders() {
+    let mut positional = QueryBuilderEnum::new(Dialect::SQLite);
+    positional.from("scores").where_raw("home >= ?").where_eq("season", QueryValue::I32(2024));
+    assert!(matches!(positional.build(), Err(Error::QueryError(message)) if message.contains("home >= ?")));
+
+    let mut short = QueryBuilderEnum::new(Dialect::SQLite);
+    short.from("scores").group_by(&["team"]).having_params("COUNT(*) > ? AND SUM(home) > ?", &[QueryValue::I32(1)]);
+    assert!(short.build().is_err());
+
+    let mut extra = QueryBuilderEnum::new(Dialect::SQLite);
+    extra.from("scores").having_params("COUNT(*) > 1", &[QueryValue::I32(1)]);
+    assert!(extra.build().is_err());
+
+    let mut join = QueryBuilderEnum::new(Dialect::SQLite);
+    join.from("scores").join_on_params("teams", "teams.id = scores.team_id AND teams.league = ?", &[], JoinType::Inner);
+    assert!(join.build().is_err());
+
+    // A `?` inside a string literal is not a placeholder
+    let mut quoted = QueryBuilderEnum::new(Dialect::SQLite);
+    quoted.from("scores").where_raw("label <> 'who?'");
+    assert!(quoted.build().is_ok());
+}
+
+#[test]
+fn test_build_with_params_returns_owned_pair() -> Result<()> {
+    let mut builder = QueryBuilderEnum::new(Dialect::SQLite);
+    builder.from("users")
+        .where_eq("name", QueryValue::from("Ann"))
+        .where_eq("age", QueryValue::I32(30));
+
+    let (sql, params) = builder.build_with_params()?;
+    builder.reset();
+
+    assert_eq!(sql, "SELECT * FROM users WHERE name = ? AND age = ?");
+    assert_eq!(params, vec![QueryValue::from("Ann"), QueryValue::I32(30)]);
+    assert!(builder.params().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_identifier_validation_rejects_injection() -> Result<()> {
+    let mut builder = QueryBuilderEnum::new(Dialect::SQLite);
+    builder.from("users").order_by("name; DROP TABLE users", OrderDirection::Asc);
+    assert!(matches!(builder.build(), Err(Error::QueryError(_))));
+
+    let mut builder = QueryBuilderEnum::new(Dialect::SQLite);
+    builder.from("users u")
+        .where_eq("u.age", QueryValue::I32(18))
+        .order_by("\"display name\"", OrderDirection::Asc);
+    assert_eq!(
+        builder.build()?,
+        "SELECT * FROM users u WHERE u.age = ? ORDER BY \"display name\" ASC"
+    );
+
+    let mut trusted = QueryBuilderEnum::new(Dialect::SQLite);
+    trusted.from("users").order_by("LOWER(name)", OrderDirection::Asc);
+    assert!(trusted.build().is_err());
+    trusted.trust_identifiers();
+    assert_eq!(trusted.build()?, "SELECT * FROM users ORDER BY LOWER(name) ASC");
+
+    Ok(())
+}
+
+#[test]
+fn test_select_window_row_number() -> Result<()> {
+    let mut builder = QueryBuilderEnum::new(Dialect::SQLite);
+    builder.select(&[Column::new("name", ColumnType::Text), Column::new("department", ColumnType::Text)])
+        .select_window("rank", "ROW_NUMBER()", &["department"], &[("salary", OrderDirection::Desc)])
+        .from("employees");
+
+    assert_eq!(
+        builder.build()?,
+        "SELECT name, department, ROW_NUMBER() OVER (PARTITION BY department ORDER BY salary DESC) AS rank FROM employees"
+    );
+    assert_eq!(orm::query::window("COUNT(*)", &[], &[]), "COUNT(*) OVER ()");
+
+    Ok(())
+}
+
+#[test]
+fn test_update_and_delete_with_join() -> Result<()> {
+    let mut update = QueryBuilderEnum::new(Dialect::MySQL);
+    update.update("orders")
+        .inner_join("customers", "customers.id = orders.customer_id")
+        .set_param("orders.status", QueryValue::from("frozen"))
+        .where_eq("customers.banned", QueryValue::Bool(true));
+    assert_eq!(
+        update.build()?,
+        "UPDATE orders INNER JOIN customers ON customers.id = orders.customer_id \
+         SET orders.status = ? WHERE customers.banned = ?"
+    );
+
+    let mut delete = QueryBuilderEnum::new(Dialect::MySQL);
+    delete.delete_from("orders")
+        .inner_join("customers", "customers.id = orders.customer_id")
+        .where_eq("customers.banned", QueryValue::Bool(true));
+    assert_eq!(
+        delete.build()?,
+        "DELETE orders FROM orders INNER JOIN customers ON customers.id = orders.customer_id \
+         WHERE customers.banned = ?"
+    );
+
+    // MySQL needs the alias as the delete target when the table has one
+    for table in ["orders o", "orders AS o"] {
+        let mut aliased = QueryBuilderEnum::new(Dialect::MySQL);
+        aliased.delete_from(table)
+            .inner_join("customers c", "c.id = o.customer_id")
+            .where_eq("c.banned", QueryValue::Bool(true));
+        assert_eq!(
+            aliased.build()?,
+            "DELETE o FROM orders AS o INNER JOIN customers c ON c.id = o.customer_id WHERE c.banned = ?"
+        );
+    }
+
+    let mut sqlite = QueryBuilderEnum::new(Dialect::SQLite);
+    sqlite.delete_from("orders").inner_join("customers", "customers.id = orders.customer_id");
+    assert!(sqlite.build().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_select_aggregate_distinct() -> Result<()> {
+    let mut builder = QueryBuilderEnum::new(Dialect::SQLite);
+    builder.select(&[Column::new("category_id", ColumnType::BigInteger)])
+        .select_aggregate("authors", "COUNT", "author_id", true)
+        .select_aggregate("total", "SUM", "views", false)
+        .from("posts")
+        .group_by(&["category_id"]);
+
+    assert_eq!(
+        builder.build()?,
+        "SELECT category_id, COUNT(DISTINCT author_id) AS authors, SUM(views) AS total FROM posts GROUP BY category_id"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_update_where_in_param_order() -> Result<()> {
+    let mut builder = QueryBuilderEnum::new(Dialect::Postgres);
+    builder.update("posts")
+        .where_in("id", &[QueryValue::I64(1), QueryValue::I64(3)])
+        .set_param("published", QueryValue::Bool(true));
+
+    let (sql, params) = builder.build_with_params()?;
+    assert_eq!(sql, "UPDATE posts SET published = $1 WHERE id IN ($2, $3)");
+    assert_eq!(params, vec![QueryValue::Bool(true), QueryValue::I64(1), QueryValue::I64(3)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_where_json_eq_per_dialect() -> Result<()> {
+    let mut sqlite = QueryBuilderEnum::new(Dialect::SQLite);
+    let sql = sqlite
+        .select(&[])
+        .from("users")
+        .where_json_eq("metadata", "profile.role", QueryValue::from("admin"))
+        .build()?;
+    assert_eq!(sql, "SELECT * FROM users WHERE json_extract(metadata, ?) = ?");
+    assert_eq!(sqlite.params(), &[QueryValue::from("$.profile.role"), QueryValue::from("admin")]);
+
+    let mut mysql = QueryBuilderEnum::new(Dialect::MySQL);
+    let sql = mysql
+        .select(&[])
+        .from("users")
+        .where_json_eq("metadata", "role", QueryValue::from("admin"))
+        .build()?;
+    assert_eq!(sql, "SELECT * FROM users WHERE JSON_UNQUOTE(JSON_EXTRACT(metadata, ?)) = ?");
+
+    let mut postgres = QueryBuilderEnum::new(Dialect::Postgres);
+    let sql = postgres
+        .select(&[])
+        .from("users")
+        .where_json_eq("metadata", "profile.role", QueryValue::from("admin"))
+        .build()?;
+    assert_eq!(sql, "SELECT * FROM users WHERE jsonb_extract_path_text(metadata, $1, $2) = $3");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_where_json_eq_filters_nested_key() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+    backend.execute("CREATE TABLE users (id INTEGER PRIMARY KEY, metadata TEXT NOT NULL)", &[]).await?;
+    for (id, metadata) in [
+        (1, r#"{"profile": {"role": "admin"}}"#),
+        (2, r#"{"profile": {"role": "member"}}"#),
+        (3, r#"{"role": "admin"}"#),
+    ] {
+        backend.execute("INSERT INTO users (id, metadata) VALUES (?, ?)", &[QueryValue::I32(id), QueryValue::from(metadata)]).await?;
+    }
+
+    let mut builder = backend.query_builder();
+    builder
+        .select(&[])
+        .from("users")
+        .where_json_eq("metadata", "profile.role", QueryValue::from("admin"));
+    let (sql, params) = builder.build_with_params()?;
+    let rows = backend.fetch_all_params(&sql, &params).await?;
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].get("id").and_then(|v| v.as_i64()), Some(1));
+
+    Ok(())
+}
+
+#[test]
+fn test_where_match_per_dialect() -> Result<()> {
+    let mut mysql = QueryBuilderEnum::new(Dialect::MySQL);
+    let sql = mysql
+        .select(&[])
+        .from("articles")
+        .where_match(&["title", "body"], QueryValue::from("rust"))
+        .build()?;
+    assert_eq!(sql, "SELECT * FROM articles WHERE MATCH (title, body) AGAINST (?)");
+    assert_eq!(mysql.params().len(), 1);
+
+    let mut sqlite = QueryBuilderEnum::new(Dialect::SQLite);
+    let sql = sqlite
+        .select(&[])
+        .from("articles")
+        .where_match(&["title", "body"], QueryValue::from("rust"))
+        .build()?;
+    assert_eq!(sql, "SELECT * FROM articles WHERE (title MATCH ? OR body MATCH ?)");
+    assert_eq!(sqlite.params().len(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_where_match_sqlite_fts5() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+    backend.execute("CREATE VIRTUAL TABLE articles USING fts5(title, body)", &[]).await?;
+    for (title, body) in [
+        ("Rust ORMs", "Mapping rows to structs"),
+        ("Gardening", "Rust on garden tools"),
+        ("Cooking", "Slow roasted vegetables"),
+    ] {
+        backend.execute("INSERT INTO articles (title, body) VALUES (?, ?)", &[QueryValue::from(title), QueryValue::from(body)]).await?;
+    }
+
+    let mut builder = backend.query_builder();
+    builder
+        .select(&[])
+        .from("articles")
+        .where_match(&["title", "body"], QueryValue::from("rust"));
+    let (sql, params) = builder.build_with_params()?;
+    assert_eq!(backend.fetch_all_params(&sql, &params).await?.len(), 2);
+
+    let mut builder = backend.query_builder();
+    builder
+        .select(&[])
+        .from("articles")
+        .where_match(&["title"], QueryValue::from("rust"));
+    let (sql, params) = builder.build_with_params()?;
+    assert_eq!(backend.fetch_all_params(&sql, &params).await?.len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_empty_where_in_matches_nothing() -> Result<()> {
+    let mut builder = QueryBuilderEnum::new(Dialect::MySQL);
+    let sql = builder.select(&[]).from("users").where_in("id", &[]).build()?;
+    assert_eq!(sql, "SELECT * FROM users WHERE 1 = 0");
+    assert!(builder.params().is_empty());
+
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+    backend.execute("CREATE TABLE users (id INTEGER PRIMARY KEY)", &[]).await?;
+    backend.execute("INSERT INTO users (id) VALUES (1), (2)", &[]).await?;
+
+    let mut builder = backend.query_builder();
+    builder.select(&[]).from("users").where_in("id", &[]);
+    let (sql, params) = builder.build_with_params()?;
+    assert!(backend.fetch_all_params(&sql, &params).await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_offset_without_limit_and_page() -> Result<()> {
+    let mut mysql = QueryBuilderEnum::new(Dialect::MySQL);
+    let sql = mysql.select(&[]).from("users").offset(20).build()?;
+    assert_eq!(sql, "SELECT * FROM users LIMIT 18446744073709551615 OFFSET 20");
+
+    let mut postgres = QueryBuilderEnum::new(Dialect::Postgres);
+    let sql = postgres.select(&[]).from("users").offset(20).build()?;
+    assert_eq!(sql, "SELECT * FROM users OFFSET 20");
+
+    let mut paged = QueryBuilderEnum::new(Dialect::MySQL);
+    let sql = paged.select(&[]).from("users").page(3, 10).build()?;
+    assert_eq!(sql, "SELECT * FROM users LIMIT 10 OFFSET 20");
+
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+    backend.execute("CREATE TABLE users (id INTEGER PRIMARY KEY)", &[]).await?;
+    backend.execute("INSERT INTO users (id) VALUES (1), (2), (3)", &[]).await?;
+
+    let mut builder = backend.query_builder();
+    let sql = builder.select(&[]).from("users").order_by("id", OrderDirection::Asc).offset(1).build()?;
+    let rows = backend.fetch_all_params(&sql, &[]).await?;
+    assert_eq!(rows.len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_values_owned_moves_strings() -> Result<()> {
+    let content = "x".repeat(1 << 20);
+    let content_ptr = content.as_ptr();
+    let value = Value::String(content).into_query_value();
+
+    let mut builder = QueryBuilderEnum::new(Dialect::SQLite);
+    let sql = builder.insert_into("posts", &["content"]).values_owned(vec![value]).build()?;
+    assert_eq!(sql, "INSERT INTO posts (content) VALUES (?)");
+
+    // The bound parameter is the buffer that was passed in, not a further copy
+    match &builder.params()[0] {
+        QueryValue::String(bound) => assert_eq!(bound.as_ptr(), content_ptr),
+        other => panic!("unexpected param {:?}", other),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_order_direction_from_str() {
+    assert_eq!("DESC".parse::<OrderDirection>().unwrap(), OrderDirection::Desc);
+    assert_eq!("asc".parse::<OrderDirection>().unwrap(), OrderDirection::Asc);
+    assert_eq!(" Desc ".parse::<OrderDirection>().unwrap(), OrderDirection::Desc);
+    assert!(matches!("sideways".parse::<OrderDirection>(), Err(Error::QueryError(_))));
+    assert_eq!(OrderDirection::default(), OrderDirection::Asc);
+}
+
+#[tokio::test]
+async fn test_full_join_by_dialect() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+    assert!(backend.supports_feature(orm::backend::BackendFeature::FullJoin));
+
+    backend.execute_batch(r#"
+        CREATE TABLE left_items (id INTEGER PRIMARY KEY, name TEXT);
+        CREATE TABLE right_items (id INTEGER PRIMARY KEY, name TEXT);
+        INSERT INTO left_items (id, name) VALUES (1, 'a'), (2, 'b');
+        INSERT INTO right_items (id, name) VALUES (2, 'B'), (3, 'C');
+    "#).await?;
+
+    let mut builder = backend.query_builder();
+    let sql = builder
+        .select(&[])
+        .from("left_items")
+        .join("right_items", "right_items.id = left_items.id", JoinType::Full)
+        .build()?;
+    assert_eq!(sql, "SELECT * FROM left_items FULL OUTER JOIN right_items ON right_items.id = left_items.id");
+    assert_eq!(backend.fetch_all_params(&sql, &[]).await?.len(), 3);
+
+    // MySQL has no FULL OUTER JOIN, so the builder refuses rather than emit invalid SQL
+    let mut mysql = QueryBuilderEnum::new(Dialect::MySQL);
+    let err = mysql
+        .select(&[])
+        .from("left_items")
+        .join("right_items", "right_items.id = left_items.id", JoinType::Full)
+        .build()
+        .unwrap_err();
+    assert!(err.to_string().contains("FULL OUTER JOIN right_items is not supported by MySQL"));
+
+    Ok(())
+}
+
+#[test]
+fn test_on_conflict_update_by_dialect() -> Result<()> {
+    let build = |dialect: Dialect, update: &[&str]| {
+        let mut builder = QueryBuilderEnum::new(dialect);
+        builder
+            .insert_into("users", &["email", "name"])
+            .values_params(&[QueryValue::from("ann@example.com"), QueryValue::from("Ann")])
+            .on_conflict_update(&["email"], update)
+            .build()
+    };
+
+    assert_eq!(
+        build(Dialect::SQLite, &["name"])?,
+        "INSERT INTO users (email, name) VALUES (?, ?) ON CONFLICT (email) DO UPDATE SET name = excluded.name"
+    );
+    assert_eq!(
+        build(Dialect::Postgres, &["name"])?,
+        "INSERT INTO users (email, name) VALUES ($1, $2) ON CONFLICT (email) DO UPDATE SET name = excluded.name"
+    );
+    assert_eq!(
+        build(Dialect::MySQL, &["name"])?,
+        "INSERT INTO users (email, name) VALUES (?, ?) ON DUPLICATE KEY UPDATE name = VALUES(name)"
+    );
+    assert_eq!(
+        build(Dialect::SQLite, &[])?,
+        "INSERT INTO users (email, name) VALUES (?, ?) ON CONFLICT (email) DO NOTHING"
+    );
+    assert_eq!(
+        build(Dialect::MySQL, &[])?,
+        "INSERT INTO users (email, name) VALUES (?, ?) ON DUPLICATE KEY UPDATE email = email"
+    );
+
+    let mut invalid = QueryBuilderEnum::new(Dialect::SQLite);
+    invalid
+        .insert_into("users", &["email"])
+        .values_params(&[QueryValue::from("x")])
+        .on_conflict_update(&["email; DROP TABLE users"], &[]);
+    assert!(invalid.build().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_full_table_guard() -> Result<()> {
+    let mut delete = QueryBuilderEnum::new(Dialect::SQLite);
+    delete.delete_from("sessions");
+    let err = delete.build().unwrap_err();
+    assert!(err.to_string().contains("DELETE without a WHERE clause"));
+
+    let mut update = QueryBuilderEnum::new(Dialect::MySQL);
+    update.update("users").set_param("active", QueryValue::Bool(false));
+    assert!(matches!(update.build(), Err(Error::QueryError(_))));
+
+    delete.allow_full_table();
+    assert_eq!(delete.build()?, "DELETE FROM sessions");
+    update.allow_full_table();
+    assert_eq!(update.build()?, "UPDATE users SET active = ?");
+
+    // reset() turns the guard back on
+    delete.reset();
+    delete.delete_from("sessions");
+    assert!(delete.build().is_err());
+
+    let mut select = QueryBuilderEnum::new(Dialect::SQLite);
+    let sql = select.select(&[]).from("users").limit_offset(10, 30).build()?;
+    assert_eq!(sql, "SELECT * FROM users LIMIT 10 OFFSET 30");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_order_by_raw_expression() -> Result<()> {
+    let mut builder = QueryBuilderEnum::new(Dialect::SQLite);
+    let sql = builder
+        .select(&[])
+        .from("users")
+        .order_by_raw("LENGTH(name)", OrderDirection::Desc)
+        .order_by("id", OrderDirection::Asc)
+        .build()?;
+    assert_eq!(sql, "SELECT * FROM users ORDER BY LENGTH(name) DESC, id ASC");
+
+    // order_by still validates its column
+    let mut invalid = QueryBuilderEnum::new(Dialect::SQLite);
+    invalid.select(&[]).from("users").order_by("LENGTH(name)", OrderDirection::Desc);
+    assert!(invalid.build().is_err());
+
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+    backend.execute("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL)", &[]).await?;
+    backend.execute("INSERT INTO users (id, name) VALUES (1, 'Al'), (2, 'Bernadette'), (3, 'Cy')", &[]).await?;
+
+    let mut builder = backend.query_builder();
+    let sql = builder.select(&[]).from("users").order_by_raw("LENGTH(name)", OrderDirection::Desc).build()?;
+    let rows = backend.fetch_all_params(&sql, &[]).await?;
+    assert_eq!(rows[0]["name"], "Bernadette");
+
+    Ok(())
+}
+
+#[test]
+fn test_strict_group_by() -> Result<()> {
+    let mut builder = QueryBuilderEnum::new(Dialect::MySQL);
+    builder
+        .select(&[Column::new("users.country", ColumnType::Text), Column::new("city", ColumnType::Text)])
+        .from("users")
+        .group_by(&["country"]);
+
+    // Off by default: the statement is left for the server to judge
+    assert!(builder.build().is_ok());
+
+    builder.strict_group_by();
+    let err = builder.build().unwrap_err();
+    assert!(err.to_string().contains("Column city must appear in GROUP BY"));
+
+    builder.group_by(&["country", "city"]);
+    builder.select_aggregate("total", "COUNT", "id", false);
+    assert_eq!(
+        builder.build()?,
+        "SELECT users.country, city, COUNT(id) AS total FROM users GROUP BY country, city"
+    );
+
+    // Differently-qualified columns are not the same column
+    let mut joined = QueryBuilderEnum::new(Dialect::SQLite);
+    joined
+        .select(&[Column::new("orders.id", ColumnType::BigInteger)])
+        .from("users")
+        .group_by(&["users.id"])
+        .strict_group_by();
+    assert!(matches!(joined.build(), Err(Error::QueryError(_))));
+
+    Ok(())
+}
+
+#[test]
+fn test_query_value_serde_round_trip() -> Result<()> {
+    let params = vec![
+        QueryValue::Null,
+        QueryValue::Bool(true),
+        QueryValue::I32(7),
+        QueryValue::I64(7),
+        QueryValue::F64(1.5),
+        QueryValue::String("O'Brien".to_string()),
+        QueryValue::Array(vec![QueryValue::I64(1), QueryValue::String("a".to_string())]),
+    ];
+
+    let json = serde_json::to_string(&params).unwrap();
+    assert!(json.starts_with(r#"["Null",{"Bool":true},{"I32":7},{"I64":7}"#));
+
+    let decoded: Vec<QueryValue> = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, params);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_to_count_sql_keeps_joins_and_filters() -> Result<()> {
+    let mut builder = QueryBuilderEnum::new(Dialect::SQLite);
+    builder
+        .select(&[Column::new("users.name", ColumnType::Text), Column::new("posts.title", ColumnType::Text)])
+        .from("users")
+        .inner_join("posts", "posts.user_id = users.id")
+        .where_eq("posts.published", QueryValue::Bool(true))
+        .order_by("posts.title", OrderDirection::Asc)
+        .limit(10)
+        .offset(20);
+
+    let (sql, params) = builder.to_count_sql()?;
+    assert_eq!(
+        sql,
+        "SELECT COUNT(*) AS count FROM users INNER JOIN posts ON posts.user_id = users.id WHERE posts.published = ?"
+    );
+    assert_eq!(params, vec![QueryValue::Bool(true)]);
+
+    let mut grouped = builder.clone();
+    grouped.select(&[Column::new("users.name", ColumnType::Text)]).group_by(&["users.name"]);
+    let (grouped_sql, _) = grouped.to_count_sql()?;
+    assert!(grouped_sql.starts_with("SELECT COUNT(*) OVER () AS count FROM users"), "{}", grouped_sql);
+    assert!(grouped_sql.ends_with("GROUP BY users.name LIMIT 1"), "{}", grouped_sql);
+
+    let mut distinct = builder.clone();
+    distinct.select(&[Column::new("users.name", ColumnType::Text)]).distinct();
+    let (distinct_sql, _) = distinct.to_count_sql()?;
+    assert!(distinct_sql.starts_with("SELECT COUNT(*) AS count FROM (SELECT DISTINCT users.name FROM users"), "{}", distinct_sql);
+
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+    backend.execute_batch(
+        "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL); \
+         CREATE TABLE posts (id INTEGER PRIMARY KEY, user_id INTEGER NOT NULL, title TEXT NOT NULL, published INTEGER NOT NULL); \
+         INSERT INTO users (id, name) VALUES (1, 'alice'), (2, 'bob'), (3, 'carol'); \
+         INSERT INTO posts (user_id, title, published) VALUES (1, 'a', 1), (1, 'b', 1), (2, 'c', 1), (3, 'd', 0);",
+    ).await?;
+
+    for (query, expected) in [(&builder, 3), (&grouped, 2), (&distinct, 2)] {
+        let (sql, params) = query.to_count_sql()?;
+        let row = backend.fetch_one_params(&sql, &params).await?.unwrap();
+        assert_eq!(row["count"], expected, "{}", sql);
+    }
+
+    let mut delete = QueryBuilderEnum::new(Dialect::SQLite);
+    delete.delete_from("users").where_eq("id", QueryValue::I64(1));
+    assert!(delete.to_count_sql().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_dialect_query_builder_factory() -> Result<()> {
+    let expected = [
+        (Dialect::SQLite, "SELECT * FROM users WHERE id = ? AND age > ?"),
+        (Dialect::MySQL, "SELECT * FROM users WHERE id = ? AND age > ?"),
+        (Dialect::Postgres, "SELECT * FROM users WHERE id = $1 AND age > $2"),
+    ];
+
+    for (dialect, sql) in expected {
+        let mut builder = dialect.query_builder();
+        builder
+            .select(&[])
+            .from("users")
+            .where_eq("id", QueryValue::I64(1))
+            .where_cmp("age", ">", QueryValue::I32(18));
+        assert_eq!(builder.build()?, sql);
+        assert_eq!(builder.params(), &[QueryValue::I64(1), QueryValue::I32(18)]);
+    }
+
+    // MySQL-only syntax is decided by the dialect alone
+    let mut full = Dialect::MySQL.query_builder();
+    full.select(&[]).from("a").join("b", "a.id = b.id", JoinType::Full);
+    assert!(full.build().is_err());
+
+    Ok(())
+}
@@ -1,5 +1,20 @@
 use orm::{prelude::*, query::QueryValue};
 
+#[derive(Debug)]
+struct Account {
+    name: String,
+    balance: i64,
+}
+
+impl FromRow for Account {
+    fn from_row(row: &orm::model::Row) -> Result<Self> {
+        Ok(Account {
+            name: row.get_string("name")?,
+            balance: row.get_i64("balance")?,
+        })
+    }
+}
+
 #[tokio::test]
 async fn test_transaction_commit() -> Result<()> {
     let db = Database::connect("sqlite::memory:").await?;
@@ -234,3 +249,239 @@ async fn test_transaction_error_handling() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_transaction_typed_fetch() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+
+    backend.execute("CREATE TABLE accounts (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, balance INTEGER NOT NULL)", &[]).await?;
+    backend.execute("INSERT INTO accounts (name, balance) VALUES ('Alice', 100), ('Bob', 50)", &[]).await?;
+
+    let mut tx = db.begin_transaction().await?;
+    tx.execute_params("UPDATE accounts SET balance = balance + ? WHERE name = ?", &[QueryValue::I64(25), QueryValue::from("Bob")]).await?;
+
+    let accounts: Vec<Account> = tx.fetch_all_as("SELECT name, balance FROM accounts ORDER BY name", &[]).await?;
+    assert_eq!(accounts.len(), 2);
+    assert_eq!(accounts[0].name, "Alice");
+    assert_eq!(accounts[1].balance, 75);
+
+    let missing: Option<Account> = tx.fetch_one_as("SELECT name, balance FROM accounts WHERE name = ?", &[QueryValue::from("Carol")]).await?;
+    assert!(missing.is_none());
+
+    tx.commit().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_read_transaction_sees_consistent_snapshot() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("orm_read_tx_{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let db = Database::connect(&format!("sqlite://{}", dir.join("app.db").display())).await?;
+    let backend = db.backend();
+    // Without WAL, SQLite readers block writers until the snapshot ends
+    backend.execute_batch("PRAGMA journal_mode = WAL").await?;
+    backend.execute("CREATE TABLE events (id INTEGER PRIMARY KEY)", &[]).await?;
+    backend.execute("INSERT INTO events (id) VALUES (1), (2)", &[]).await?;
+
+    let count_sql = "SELECT COUNT(*) AS n FROM events";
+    let mut tx = db.begin_read_transaction().await?;
+    assert!(tx.is_read_only());
+    let before = tx.fetch_one_params(count_sql, &[]).await?.unwrap();
+
+    // A write committed elsewhere is not visible inside the snapshot
+    backend.execute("INSERT INTO events (id) VALUES (3)", &[]).await?;
+    let after = tx.fetch_one_params(count_sql, &[]).await?.unwrap();
+    assert_eq!(before.get("n"), after.get("n"));
+
+    let write = tx.execute_params("DELETE FROM events", &[]).await;
+    assert!(matches!(write, Err(Error::TransactionError(_))));
+    tx.rollback().await?;
+
+    let latest = backend.fetch_one_params(count_sql, &[]).await?.unwrap();
+    assert_eq!(latest.get("n").and_then(|v| v.as_i64()), Some(3));
+
+    db.close().await;
+    std::fs::remove_dir_all(&dir).ok();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_transaction_is_open_and_closed_errors() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+
+    let mut tx = db.begin_transaction().await?;
+    assert!(tx.is_open());
+    tx.fetch_all_params("SELECT 1", &[]).await?;
+    tx.commit().await?;
+    assert!(!tx.is_open());
+
+    assert!(matches!(tx.fetch_all_params("SELECT 1", &[]).await, Err(Error::TransactionClosed)));
+    assert!(matches!(tx.execute_params("SELECT 1", &[]).await, Err(Error::TransactionClosed)));
+    assert!(matches!(tx.commit().await, Err(Error::TransactionClosed)));
+    assert!(matches!(tx.rollback().await, Err(Error::TransactionClosed)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_transaction_retry_recovers_from_busy_database() -> Result<()> {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use tokio::sync::Mutex;
+
+    let dir = std::env::temp_dir().join(format!("orm_tx_retry_{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let url = format!("sqlite:{}?mode=rwc", dir.join("app.db").display());
+    let db = Database::connect(&url).await?;
+    db.backend().execute("CREATE TABLE events (id INTEGER PRIMARY KEY, name TEXT NOT NULL)", &[]).await?;
+
+    // A second writer holds the database lock until the first attempt has failed
+    let mut blocker = db.begin_transaction().await?;
+    blocker.execute_params("INSERT INTO events (name) VALUES ('blocker')", &[]).await?;
+    let blocker = Arc::new(Mutex::new(Some(blocker)));
+
+    let attempts = Arc::new(AtomicU32::new(0));
+    let id = db.transaction_retry(3, |tx| {
+        let attempts = attempts.clone();
+        let blocker = blocker.clone();
+        Box::pin(async move {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            tx.execute_params("PRAGMA busy_timeout = 0", &[]).await?;
+            let inserted = tx.execute_params("INSERT INTO events (name) VALUES (?)", &[QueryValue::from("signup")]).await;
+            if let Some(mut blocker) = blocker.lock().await.take() {
+                blocker.rollback().await?;
+            }
+            inserted?;
+            let row = tx.fetch_one_params("SELECT MAX(id) AS id FROM events", &[]).await?;
+            Ok(row.and_then(|r| r.get("id").and_then(|v| v.as_i64())))
+        })
+    }).await?;
+
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    assert_eq!(id, Some(1));
+
+    // Non-retryable errors are returned after a single attempt
+    attempts.store(0, Ordering::SeqCst);
+    let result: Result<()> = db.transaction_retry(3, |tx| {
+        let attempts = attempts.clone();
+        Box::pin(async move {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            tx.execute_params("INSERT INTO missing (id) VALUES (1)", &[]).await?;
+            Ok(())
+        })
+    }).await;
+    assert!(result.is_err());
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+    db.close().await;
+    std::fs::remove_dir_all(&dir).ok();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_transaction_execute_batch_rolls_back() -> Result<()> {
+    let db = Database::connect("sqlite::memory:").await?;
+    let backend = db.backend();
+    backend.execute("CREATE TABLE accounts (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, balance INTEGER NOT NULL)", &[]).await?;
+
+    let mut tx = db.begin_transaction().await?;
+    let affected = tx.execute_batch(r#"
+        INSERT INTO accounts (name, balance) VALUES ('Alice', 100);
+        INSERT INTO accounts (name, balance) VALUES ('Bob', 50), ('Carol', 75);
+    "#).await?;
+    assert_eq!(affected, 3);
+
+    let inside = tx.fetch_all_params("SELECT name FROM accounts", &[]).await?;
+    assert_eq!(inside.len(), 3);
+    tx.rollback().await?;
+
+    let outside = backend.fetch_all_params("SELECT name FROM accounts", &[]).await?;
+    assert!(outside.is_empty());
+
+    // A failing statement leaves the earlier ones for the caller to roll back
+    let mut tx = db.begin_transaction().await?;
+    let failed = tx.execute_batch("INSERT INTO accounts (name, balance) VALUES ('Dan', 10); INSERT INTO missing VALUES (1);").await;
+    assert!(failed.is_err());
+    tx.rollback().await?;
+    assert!(backend.fetch_all_params("SELECT name FROM accounts", &[]).await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_busy_snapshot_is_retryable() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("orm_busy_snapshot_{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let url = format!("sqlite:{}?mode=rwc", dir.join("app.db").display());
+    let db = Database::connect(&url).await?;
+    db.backend().execute_batch("PRAGMA journal_mode = WAL; CREATE TABLE events (id INTEGER PRIMARY KEY, name TEXT NOT NULL);").await?;
+
+    // The reader's snapshot goes stale when another connection commits, so its
+    // upgrade to a writer fails with SQLITE_BUSY_SNAPSHOT rather than plain SQLITE_BUSY
+    let mut reader = db.begin_transaction().await?;
+    reader.fetch_all_params("SELECT * FROM events", &[]).await?;
+    db.backend().execute("INSERT INTO events (name) VALUES ('committed')", &[]).await?;
+    let err = reader.execute_params("INSERT INTO events (name) VALUES ('stale')", &[]).await.unwrap_err();
+    reader.rollback().await?;
+
+    let code = match &err {
+        Error::DatabaseError(sqlx::Error::Database(db)) => db.code().map(|code| code.into_owned()),
+        _ => None,
+    };
+    assert_eq!(code.as_deref(), Some("517"), "{:?}", err);
+    assert!(err.is_retryable_transaction_error());
+    assert!(err.is_transient());
+
+    db.close().await;
+    std::fs::remove_dir_all(&dir).ok();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_read_transaction_rejects_writes_on_every_path() -> Result<()> {
+    use orm::connection::ConnectOptions;
+
+    // One connection, so each check below reuses the connection the read transaction had
+    let db = Database::connect_with("sqlite::memory:", &ConnectOptions::new().max_connections(1)).await?;
+    db.backend().execute("CREATE TABLE events (id INTEGER PRIMARY KEY, name TEXT NOT NULL)", &[]).await?;
+    let insert_returning = "INSERT INTO events (name) VALUES ('sneaky') RETURNING id";
+
+    let mut tx = db.begin_read_transaction().await?;
+    assert!(tx.fetch_one_params(insert_returning, &[]).await.is_err());
+    assert!(tx.fetch_all_params(insert_returning, &[]).await.is_err());
+    tx.commit().await?;
+    db.backend().execute("INSERT INTO events (name) VALUES ('after commit')", &[]).await?;
+
+    let mut tx = db.begin_read_transaction().await?;
+    assert!(tx.fetch_one_params(insert_returning, &[]).await.is_err());
+    tx.rollback().await?;
+    db.backend().execute("INSERT INTO events (name) VALUES ('after rollback')", &[]).await?;
+
+    // Dropping without commit or rollback also restores writes on the connection
+    let tx = db.begin_read_transaction().await?;
+    drop(tx);
+    db.backend().execute("INSERT INTO events (name) VALUES ('after drop')", &[]).await?;
+
+    let count = db.backend().fetch_one_params("SELECT COUNT(*) AS n FROM events", &[]).await?.unwrap();
+    assert_eq!(count["n"], 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_read_transaction_dropped_outside_runtime_restores_writes() -> Result<()> {
+    use orm::connection::ConnectOptions;
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let db = runtime.block_on(Database::connect_with("sqlite::memory:", &ConnectOptions::new().max_connections(1)))?;
+    runtime.block_on(db.backend().execute("CREATE TABLE events (id INTEGER PRIMARY KEY, name TEXT NOT NULL)", &[]))?;
+
+    let tx = runtime.block_on(db.begin_read_transaction())?;
+    assert!(tokio::runtime::Handle::try_current().is_err());
+    drop(tx);
+
+    runtime.block_on(db.backend().execute("INSERT INTO events (name) VALUES ('after drop')", &[]))?;
+
+    Ok(())
+}